@@ -1,29 +1,25 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 
+use sample::indexed::IndexedTheGoose;
 use sample::*;
 
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("play game", |b| {
         b.iter(|| {
-            let mut the_goose = SimpleTheGoose::new((1u32..7u32).cycle());
+            let mut the_goose =
+                SimpleTheGoose::new([[1u32, 2], [3, 4], [5, 6]].into_iter().cycle());
 
             let players = vec!["Pippo", "Pluto", "Paperino"];
             for player in &players {
-                the_goose
-                    .execute(Command::Add(*player))
-                    .expect("Adding player");
+                collect(&mut the_goose, Command::Add(*player)).expect("Adding player");
             }
 
             'outher: loop {
                 for player in &players {
-                    if the_goose
-                        .execute(Command::RollAndMove(*player))
+                    if collect(&mut the_goose, Command::RollAndMove(*player))
                         .unwrap()
                         .iter()
-                        .any(|event| match event {
-                            Event::Win(_) => true,
-                            _ => false,
-                        })
+                        .any(|event| matches!(event, Event::Win(_)))
                     {
                         break 'outher;
                     }
@@ -33,5 +29,74 @@ fn criterion_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, criterion_benchmark);
+#[derive(Default)]
+struct Winner(bool);
+
+impl Events<u32, SamplePosition, [u32; 2]> for Winner {
+    type Error = std::convert::Infallible;
+
+    fn notify(&mut self, event: Event<u32, SamplePosition, [u32; 2]>) -> Result<(), Self::Error> {
+        if let Event::Win(_) = event {
+            self.0 = true;
+        }
+
+        Ok(())
+    }
+}
+
+fn simulate<G, II>(mut the_goose: G, pawns: u32, rounds: usize)
+where
+    G: TheGoose<u32, SamplePosition, [u32; 2], II>,
+    G::State: State<u32, SamplePosition, II, Error = std::convert::Infallible>,
+    II: IntoIterator<Item = u32>,
+{
+    let mut winner = Winner::default();
+
+    for pawn in 0..pawns {
+        the_goose
+            .execute(Command::Add(pawn), &mut winner)
+            .expect("Adding pawn");
+    }
+
+    for _ in 0..rounds {
+        for pawn in 0..pawns {
+            the_goose
+                .execute(Command::RollAndMove(pawn), &mut winner)
+                .unwrap();
+            if winner.0 {
+                return;
+            }
+        }
+    }
+}
+
+fn state_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("large simulation");
+    group.sample_size(10);
+
+    for pawns in [100, 300] {
+        group.bench_with_input(BenchmarkId::new("sample", pawns), &pawns, |b, &pawns| {
+            b.iter(|| {
+                simulate(
+                    SampleTheGoose::<_, SamplePosition>::with_die(SampleDie::new(1..=6, 7)),
+                    pawns,
+                    10,
+                )
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("indexed", pawns), &pawns, |b, &pawns| {
+            b.iter(|| {
+                simulate(
+                    IndexedTheGoose::with_die(SampleDie::new(1..=6, 7)),
+                    pawns,
+                    10,
+                )
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark, state_benchmark);
 criterion_main!(benches);
@@ -0,0 +1,576 @@
+use std::cell::Cell;
+use std::future::{ready, Future};
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use the_goose::{asynch, ExtraTurns, GooseRule, Turn};
+
+use crate::{Event, Events, SamplePosition, SampleTheGoose, State};
+
+#[derive(Default, PartialEq, Debug)]
+pub struct Adapter<T>(pub T);
+
+impl<T> Deref for Adapter<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Adapter<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<Player, Position, Roll, T> asynch::Events<Player, Position, Roll> for Adapter<T>
+where
+    T: Events<Player, Position, Roll>,
+{
+    type Error = T::Error;
+
+    fn notify(
+        &mut self,
+        event: Event<Player, Position, Roll>,
+    ) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(self.0.notify(event))
+    }
+
+    fn begin(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(self.0.begin())
+    }
+
+    fn commit(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(self.0.commit())
+    }
+
+    fn rollback(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(self.0.rollback())
+    }
+}
+
+impl<Player, Position, II, T> asynch::State<Player, Position, II> for Adapter<T>
+where
+    II: IntoIterator<Item = Player>,
+    T: State<Player, Position, II>,
+{
+    type Error = T::Error;
+
+    fn get_player_position(
+        &self,
+        player: &Player,
+    ) -> impl Future<Output = Result<Option<Position>, Self::Error>> {
+        ready(self.0.get_player_position(player))
+    }
+
+    fn add_player(&mut self, player: Player) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(self.0.add_player(player))
+    }
+
+    fn remove_player(&mut self, player: &Player) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(self.0.remove_player(player))
+    }
+
+    fn find_players_by_position(
+        &self,
+        position: &Position,
+    ) -> impl Future<Output = Result<II, Self::Error>> {
+        ready(self.0.find_players_by_position(position))
+    }
+
+    fn players(&self) -> impl Future<Output = Result<II, Self::Error>> {
+        ready(self.0.players())
+    }
+
+    fn update_player_position(
+        &mut self,
+        player: &Player,
+        position: &Position,
+    ) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(self.0.update_player_position(player, position))
+    }
+
+    fn begin(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(self.0.begin())
+    }
+
+    fn commit(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(self.0.commit())
+    }
+
+    fn rollback(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(self.0.rollback())
+    }
+
+    fn turn(&self) -> impl Future<Output = Result<Turn<Player>, Self::Error>> {
+        ready(self.0.turn())
+    }
+
+    fn update_turn(&mut self, turn: Turn<Player>) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(self.0.update_turn(turn))
+    }
+
+    fn update_winner(
+        &mut self,
+        winner: Option<&Player>,
+    ) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(self.0.update_winner(winner))
+    }
+}
+
+impl<Player> asynch::TheGoose<Player, SamplePosition, [u32; 2], Vec<Player>>
+    for Adapter<SampleTheGoose<Player, SamplePosition>>
+where
+    Player: Eq + std::hash::Hash + Clone + std::cmp::Ord,
+{
+    type State = Self;
+
+    fn state(&self) -> &Self::State {
+        self
+    }
+
+    fn state_mut(&mut self) -> &mut Self::State {
+        self
+    }
+
+    fn roll_dice(&mut self) -> [u32; 2] {
+        [self.0.die.roll(), self.0.die.roll()]
+    }
+
+    fn is_valid_face(&self, face: &u32) -> bool {
+        self.0.die().contains(face)
+    }
+
+    fn goose_rule(&self) -> GooseRule {
+        self.0.goose_rule
+    }
+
+    fn extra_turns(&self) -> ExtraTurns {
+        self.0.extra_turns
+    }
+}
+
+struct Delay(usize);
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.0 == 0 {
+            Poll::Ready(())
+        } else {
+            self.0 -= 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub enum LatencyError<E> {
+    Unavailable,
+    Inner(E),
+}
+
+pub struct LatencyState<S> {
+    inner: S,
+    latency: usize,
+    calls: Cell<usize>,
+    failing_at: Option<usize>,
+}
+
+impl<S> LatencyState<S> {
+    pub fn new(inner: S, latency: usize) -> Self {
+        LatencyState {
+            inner,
+            latency,
+            calls: Cell::new(0),
+            failing_at: None,
+        }
+    }
+
+    pub fn failing_at(self, call: usize) -> Self {
+        LatencyState {
+            failing_at: Some(call),
+            ..self
+        }
+    }
+
+    pub fn calls(&self) -> usize {
+        self.calls.get()
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    async fn call<E>(&self) -> Result<(), LatencyError<E>> {
+        let call = self.calls.get();
+        self.calls.set(call + 1);
+
+        Delay(self.latency).await;
+
+        if self.failing_at == Some(call) {
+            Err(LatencyError::Unavailable)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<Player, Position, II, S> asynch::State<Player, Position, II> for LatencyState<S>
+where
+    II: IntoIterator<Item = Player>,
+    S: State<Player, Position, II>,
+{
+    type Error = LatencyError<S::Error>;
+
+    async fn get_player_position(&self, player: &Player) -> Result<Option<Position>, Self::Error> {
+        self.call().await?;
+        self.inner
+            .get_player_position(player)
+            .map_err(LatencyError::Inner)
+    }
+
+    async fn add_player(&mut self, player: Player) -> Result<(), Self::Error> {
+        self.call().await?;
+        self.inner.add_player(player).map_err(LatencyError::Inner)
+    }
+
+    async fn remove_player(&mut self, player: &Player) -> Result<(), Self::Error> {
+        self.call().await?;
+        self.inner
+            .remove_player(player)
+            .map_err(LatencyError::Inner)
+    }
+
+    async fn find_players_by_position(&self, position: &Position) -> Result<II, Self::Error> {
+        self.call().await?;
+        self.inner
+            .find_players_by_position(position)
+            .map_err(LatencyError::Inner)
+    }
+
+    async fn players(&self) -> Result<II, Self::Error> {
+        self.call().await?;
+        self.inner.players().map_err(LatencyError::Inner)
+    }
+
+    async fn update_player_position(
+        &mut self,
+        player: &Player,
+        position: &Position,
+    ) -> Result<(), Self::Error> {
+        self.call().await?;
+        self.inner
+            .update_player_position(player, position)
+            .map_err(LatencyError::Inner)
+    }
+
+    async fn begin(&mut self) -> Result<(), Self::Error> {
+        self.inner.begin().map_err(LatencyError::Inner)
+    }
+
+    async fn commit(&mut self) -> Result<(), Self::Error> {
+        self.inner.commit().map_err(LatencyError::Inner)
+    }
+
+    async fn rollback(&mut self) -> Result<(), Self::Error> {
+        self.inner.rollback().map_err(LatencyError::Inner)
+    }
+
+    async fn turn(&self) -> Result<Turn<Player>, Self::Error> {
+        self.call().await?;
+        self.inner.turn().map_err(LatencyError::Inner)
+    }
+
+    async fn update_turn(&mut self, turn: Turn<Player>) -> Result<(), Self::Error> {
+        self.call().await?;
+        self.inner.update_turn(turn).map_err(LatencyError::Inner)
+    }
+
+    async fn update_winner(&mut self, winner: Option<&Player>) -> Result<(), Self::Error> {
+        self.call().await?;
+        self.inner
+            .update_winner(winner)
+            .map_err(LatencyError::Inner)
+    }
+}
+
+impl<Player> asynch::TheGoose<Player, SamplePosition, [u32; 2], Vec<Player>>
+    for LatencyState<SampleTheGoose<Player, SamplePosition>>
+where
+    Player: Eq + std::hash::Hash + Clone + std::cmp::Ord,
+{
+    type State = Self;
+
+    fn state(&self) -> &Self::State {
+        self
+    }
+
+    fn state_mut(&mut self) -> &mut Self::State {
+        self
+    }
+
+    fn roll_dice(&mut self) -> [u32; 2] {
+        [self.inner.die.roll(), self.inner.die.roll()]
+    }
+
+    fn is_valid_face(&self, face: &u32) -> bool {
+        self.inner.die().contains(face)
+    }
+
+    fn goose_rule(&self) -> GooseRule {
+        self.inner.goose_rule
+    }
+
+    fn extra_turns(&self) -> ExtraTurns {
+        self.inner.extra_turns
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::landing;
+    use crate::{Cause, Command, Error, PositionType, SampleDie, SampleEvents};
+    use asynch::TheGoose;
+    use std::convert::Infallible;
+    use std::pin::pin;
+    use std::task::Waker;
+
+    fn block_on<F: Future>(future: F) -> (F::Output, usize) {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+
+        let mut polls = 1;
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return (output, polls);
+            }
+            polls += 1;
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn execute<G>(
+        the_goose: &mut G,
+        command: Command<&'static str, [u32; 2]>,
+    ) -> (
+        Result<
+            SampleEvents<&'static str, SamplePosition, [u32; 2]>,
+            Error<
+                &'static str,
+                u32,
+                <G::State as asynch::State<&'static str, SamplePosition, Vec<&'static str>>>::Error,
+                Infallible,
+            >,
+        >,
+        usize,
+    )
+    where
+        G: TheGoose<&'static str, SamplePosition, [u32; 2], Vec<&'static str>>,
+    {
+        let mut events = Adapter(SampleEvents::default());
+        let (result, polls) = block_on(the_goose.execute(command, &mut events));
+
+        (result.map(|()| events.0), polls)
+    }
+
+    #[test]
+    fn test_adapter() {
+        let mut the_goose = Adapter(SampleTheGoose::<_, SamplePosition>::new());
+
+        assert_eq!(
+            execute(&mut the_goose, Command::Add("Pippo")),
+            (Ok(vec![Event::PlayerJoined("Pippo")].into()), 1)
+        );
+
+        assert_eq!(
+            execute(&mut the_goose, Command::Add("Pippo")).0,
+            Err(Error::DuplicatePlayer("Pippo"))
+        );
+
+        the_goose.insert("Pluto", SamplePosition(17));
+
+        assert_eq!(
+            execute(&mut the_goose, Command::Move("Pippo", [4, 1])).0,
+            Ok(vec![
+                Event::Roll("Pippo", [4, 1]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(0),
+                    SamplePosition(5),
+                    landing(PositionType::TheGoose, Cause::Roll, 0)
+                ),
+                Event::MovedAgain(
+                    "Pippo",
+                    SamplePosition(5),
+                    SamplePosition(10),
+                    landing(PositionType::Normal, Cause::TheGoose, 1)
+                )
+            ]
+            .into())
+        );
+
+        assert_eq!(
+            execute(&mut the_goose, Command::Move("Pippo", [6, 1])).0,
+            Err(Error::NotYourTurn("Pippo"))
+        );
+
+        execute(&mut the_goose, Command::Move("Pluto", [1, 1]))
+            .0
+            .unwrap();
+
+        assert_eq!(
+            execute(&mut the_goose, Command::Move("Pippo", [6, 3])).0,
+            Ok(vec![
+                Event::Roll("Pippo", [6, 3]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(10),
+                    SamplePosition(19),
+                    landing(PositionType::Normal, Cause::Roll, 0)
+                ),
+                Event::Prank("Pluto", SamplePosition(19), SamplePosition(10))
+            ]
+            .into())
+        );
+    }
+
+    #[test]
+    fn test_goose_to_goose() {
+        let mut the_goose = Adapter(
+            SampleTheGoose::<_, SamplePosition>::new().with_goose_rule(GooseRule::GooseToGoose),
+        );
+
+        the_goose.insert("Pippo", SamplePosition(3));
+
+        assert_eq!(
+            execute(&mut the_goose, Command::Move("Pippo", [1, 1])).0,
+            Ok(vec![
+                Event::Roll("Pippo", [1, 1]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(3),
+                    SamplePosition(5),
+                    landing(PositionType::TheGoose, Cause::Roll, 0)
+                ),
+                Event::GooseToGoose(
+                    "Pippo",
+                    SamplePosition(5),
+                    SamplePosition(9),
+                    landing(PositionType::TheGoose, Cause::GooseToGoose, 1)
+                ),
+                Event::ExtraTurn("Pippo")
+            ]
+            .into())
+        );
+        assert_eq!(the_goose.get("Pippo"), Some(&SamplePosition(9)));
+
+        let mut the_goose = LatencyState::new(
+            SampleTheGoose::<_, SamplePosition>::new()
+                .with_goose_rule(GooseRule::GooseToGoose)
+                .with_extra_turns(ExtraTurns {
+                    doubles: true,
+                    ..ExtraTurns::default()
+                }),
+            1,
+        );
+
+        the_goose.inner.insert("Pippo", SamplePosition(10));
+        the_goose.inner.insert("Pluto", SamplePosition(18));
+
+        assert_eq!(
+            execute(&mut the_goose, Command::Move("Pippo", [2, 2])).0,
+            Ok(vec![
+                Event::Roll("Pippo", [2, 2]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(10),
+                    SamplePosition(14),
+                    landing(PositionType::TheGoose, Cause::Roll, 0)
+                ),
+                Event::GooseToGoose(
+                    "Pippo",
+                    SamplePosition(14),
+                    SamplePosition(18),
+                    landing(PositionType::TheGoose, Cause::GooseToGoose, 1)
+                ),
+                Event::Prank("Pluto", SamplePosition(18), SamplePosition(10)),
+                Event::ExtraTurn("Pippo")
+            ]
+            .into())
+        );
+        assert_eq!(the_goose.inner().get("Pluto"), Some(&SamplePosition(10)));
+
+        assert_eq!(
+            execute(&mut the_goose, Command::Move("Pippo", [3, 3])).0,
+            Ok(vec![
+                Event::Roll("Pippo", [3, 3]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(18),
+                    SamplePosition(24),
+                    landing(PositionType::Normal, Cause::Roll, 0)
+                ),
+                Event::ExtraTurn("Pippo")
+            ]
+            .into())
+        );
+    }
+
+    #[test]
+    fn test_latency() {
+        let mut the_goose = LatencyState::new(
+            SampleTheGoose::<_, SamplePosition>::with_die(SampleDie::new(1..=1, 0)),
+            3,
+        );
+
+        let (events, polls) = execute(&mut the_goose, Command::Add("Pippo"));
+        assert_eq!(events, Ok(vec![Event::PlayerJoined("Pippo")].into()));
+        assert_eq!(the_goose.calls(), 2);
+        assert_eq!(polls, 2 * 3 + 1);
+
+        let (events, _) = execute(&mut the_goose, Command::RollAndMove("Pippo"));
+        assert_eq!(
+            events,
+            Ok(vec![
+                Event::Roll("Pippo", [1, 1]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(0),
+                    SamplePosition(2),
+                    landing(PositionType::Normal, Cause::Roll, 0)
+                )
+            ]
+            .into())
+        );
+        assert_eq!(the_goose.inner().get("Pippo"), Some(&SamplePosition(2)));
+    }
+
+    #[test]
+    fn test_latency_errors() {
+        let mut the_goose = LatencyState::new(SampleTheGoose::<_, SamplePosition>::new(), 1);
+
+        execute(&mut the_goose, Command::Add("Pippo")).0.ok();
+
+        let mut the_goose = LatencyState::new(the_goose.inner, 1).failing_at(2);
+
+        assert_eq!(
+            execute(&mut the_goose, Command::Move("Pippo", [1, 2])).0,
+            Err(Error::Inner(LatencyError::Unavailable))
+        );
+        assert_eq!(the_goose.inner().get("Pippo"), Some(&SamplePosition(0)));
+
+        let mut the_goose = LatencyState::new(the_goose.inner, 1).failing_at(0);
+
+        assert_eq!(
+            execute(&mut the_goose, Command::Add("Pluto")).0,
+            Err(Error::Inner(LatencyError::Unavailable))
+        );
+        assert_eq!(the_goose.inner().get("Pluto"), None);
+    }
+}
@@ -0,0 +1,377 @@
+use std::convert::Infallible;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+
+use the_goose::EventKind;
+
+use crate::{Event, Events};
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Subscription(usize);
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Filter<Player> {
+    players: Option<Vec<Player>>,
+    kinds: Option<Vec<EventKind>>,
+}
+
+impl<Player> Filter<Player> {
+    pub fn all() -> Self {
+        Filter {
+            players: None,
+            kinds: None,
+        }
+    }
+
+    pub fn players(self, players: impl IntoIterator<Item = Player>) -> Self {
+        Filter {
+            players: Some(players.into_iter().collect()),
+            ..self
+        }
+    }
+
+    pub fn kinds(self, kinds: impl IntoIterator<Item = EventKind>) -> Self {
+        Filter {
+            kinds: Some(kinds.into_iter().collect()),
+            ..self
+        }
+    }
+}
+
+impl<Player> Default for Filter<Player> {
+    fn default() -> Self {
+        Filter::all()
+    }
+}
+
+impl<Player: PartialEq> Filter<Player> {
+    pub fn matches<Position, Roll>(&self, event: &Event<Player, Position, Roll>) -> bool {
+        let player = match (&self.players, event) {
+            (Some(players), Event::PlayerRenamed(from, to)) => {
+                players.contains(from) || players.contains(to)
+            }
+            (Some(players), _) => event.player().is_none_or(|p| players.contains(p)),
+            (None, _) => true,
+        };
+        let kind = match &self.kinds {
+            Some(kinds) => kinds.contains(&event.kind()),
+            None => true,
+        };
+
+        player && kind
+    }
+}
+
+#[allow(clippy::type_complexity)]
+enum Observer<Player, Position, Roll> {
+    Callback(Box<dyn FnMut(&Event<Player, Position, Roll>)>),
+    Channel(SyncSender<Event<Player, Position, Roll>>, usize),
+}
+
+#[allow(clippy::type_complexity)]
+pub struct Bus<Player, Position, Roll> {
+    observers: Vec<(
+        Subscription,
+        Filter<Player>,
+        Observer<Player, Position, Roll>,
+    )>,
+    pending: Option<Vec<Event<Player, Position, Roll>>>,
+    next: usize,
+}
+
+impl<Player, Position, Roll> Bus<Player, Position, Roll> {
+    pub fn new() -> Self {
+        Bus {
+            observers: Vec::new(),
+            pending: None,
+            next: 0,
+        }
+    }
+
+    pub fn subscribe(
+        &mut self,
+        filter: Filter<Player>,
+        callback: impl FnMut(&Event<Player, Position, Roll>) + 'static,
+    ) -> Subscription {
+        self.register(filter, Observer::Callback(Box::new(callback)))
+    }
+
+    pub fn channel(
+        &mut self,
+        filter: Filter<Player>,
+        capacity: usize,
+    ) -> (Subscription, Receiver<Event<Player, Position, Roll>>) {
+        let (sender, receiver) = sync_channel(capacity);
+
+        (
+            self.register(filter, Observer::Channel(sender, 0)),
+            receiver,
+        )
+    }
+
+    /// How many events a channel subscriber missed because its channel was full.
+    pub fn dropped(&self, subscription: Subscription) -> usize {
+        self.observers
+            .iter()
+            .find_map(|(s, _, observer)| match observer {
+                Observer::Channel(_, dropped) if *s == subscription => Some(*dropped),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn unsubscribe(&mut self, subscription: Subscription) -> bool {
+        let len = self.observers.len();
+        self.observers.retain(|(s, _, _)| *s != subscription);
+
+        self.observers.len() != len
+    }
+
+    pub fn len(&self) -> usize {
+        self.observers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.observers.is_empty()
+    }
+
+    fn register(
+        &mut self,
+        filter: Filter<Player>,
+        observer: Observer<Player, Position, Roll>,
+    ) -> Subscription {
+        let subscription = Subscription(self.next);
+        self.next += 1;
+        self.observers.push((subscription, filter, observer));
+
+        subscription
+    }
+
+    fn publish(&mut self, event: Event<Player, Position, Roll>)
+    where
+        Player: PartialEq,
+        Event<Player, Position, Roll>: Clone,
+    {
+        self.observers.retain_mut(|(_, filter, observer)| {
+            if !filter.matches(&event) {
+                return true;
+            }
+
+            match observer {
+                Observer::Callback(callback) => {
+                    callback(&event);
+                    true
+                }
+                Observer::Channel(sender, dropped) => match sender.try_send(event.clone()) {
+                    Ok(()) => true,
+                    Err(TrySendError::Full(_)) => {
+                        *dropped += 1;
+                        true
+                    }
+                    Err(TrySendError::Disconnected(_)) => false,
+                },
+            }
+        });
+    }
+}
+
+impl<Player, Position, Roll> Default for Bus<Player, Position, Roll> {
+    fn default() -> Self {
+        Bus::new()
+    }
+}
+
+impl<Player, Position, Roll> Events<Player, Position, Roll> for Bus<Player, Position, Roll>
+where
+    Player: PartialEq,
+    Event<Player, Position, Roll>: Clone,
+{
+    type Error = Infallible;
+
+    fn notify(&mut self, event: Event<Player, Position, Roll>) -> Result<(), Self::Error> {
+        match &mut self.pending {
+            Some(pending) => pending.push(event),
+            None => self.publish(event),
+        }
+
+        Ok(())
+    }
+
+    fn begin(&mut self) -> Result<(), Self::Error> {
+        self.pending = Some(Vec::new());
+
+        Ok(())
+    }
+
+    fn commit(&mut self) -> Result<(), Self::Error> {
+        for event in self.pending.take().into_iter().flatten() {
+            self.publish(event);
+        }
+
+        Ok(())
+    }
+
+    fn rollback(&mut self) -> Result<(), Self::Error> {
+        self.pending = None;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::landing;
+    use crate::{collect, Cause, Command, PositionType, SamplePosition, SampleTheGoose, TheGoose};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type SampleBus = Bus<&'static str, SamplePosition, [u32; 2]>;
+
+    fn the_goose() -> SampleTheGoose<&'static str, SamplePosition> {
+        let mut the_goose = SampleTheGoose::new();
+
+        collect(&mut the_goose, Command::Add("Pippo")).ok();
+        collect(&mut the_goose, Command::Add("Pluto")).ok();
+        the_goose.insert("Pluto", SamplePosition(10));
+
+        the_goose
+    }
+
+    #[test]
+    fn test_filters() {
+        let mut the_goose = the_goose();
+        let mut bus = SampleBus::new();
+
+        let all = Rc::new(RefCell::new(Vec::new()));
+        let pluto = Rc::new(RefCell::new(Vec::new()));
+        let moves = Rc::new(RefCell::new(Vec::new()));
+
+        let observed = all.clone();
+        bus.subscribe(Filter::all(), move |event| {
+            observed.borrow_mut().push(event.clone())
+        });
+        let observed = pluto.clone();
+        bus.subscribe(Filter::all().players(["Pluto"]), move |event| {
+            observed.borrow_mut().push(event.clone())
+        });
+        let observed = moves.clone();
+        bus.subscribe(
+            Filter::all().kinds([EventKind::Moved, EventKind::MovedAgain]),
+            move |event| observed.borrow_mut().push(event.kind()),
+        );
+
+        the_goose
+            .execute(Command::Move("Pippo", [4, 1]), &mut bus)
+            .unwrap();
+        the_goose
+            .execute(Command::Add("Paperino"), &mut bus)
+            .unwrap();
+        the_goose
+            .execute(Command::Rename("Pluto", "Topolino"), &mut bus)
+            .unwrap();
+
+        assert_eq!(
+            *all.borrow(),
+            vec![
+                Event::Roll("Pippo", [4, 1]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(0),
+                    SamplePosition(5),
+                    landing(PositionType::TheGoose, Cause::Roll, 0)
+                ),
+                Event::MovedAgain(
+                    "Pippo",
+                    SamplePosition(5),
+                    SamplePosition(10),
+                    landing(PositionType::Normal, Cause::TheGoose, 1)
+                ),
+                Event::Prank("Pluto", SamplePosition(10), SamplePosition(0)),
+                Event::PlayerJoined("Paperino"),
+                Event::PlayerRenamed("Pluto", "Topolino"),
+            ]
+        );
+        assert_eq!(
+            *pluto.borrow(),
+            vec![
+                Event::Prank("Pluto", SamplePosition(10), SamplePosition(0)),
+                Event::PlayerRenamed("Pluto", "Topolino"),
+            ]
+        );
+        assert_eq!(
+            *moves.borrow(),
+            vec![EventKind::Moved, EventKind::MovedAgain]
+        );
+    }
+
+    #[test]
+    fn test_unsubscribe() {
+        let mut the_goose = the_goose();
+        let mut bus = SampleBus::new();
+
+        let count = Rc::new(RefCell::new(0));
+        let observed = count.clone();
+        let subscription = bus.subscribe(Filter::all(), move |_| *observed.borrow_mut() += 1);
+
+        the_goose
+            .execute(Command::Move("Pippo", [1, 1]), &mut bus)
+            .unwrap();
+        assert_eq!(*count.borrow(), 2);
+
+        assert!(bus.unsubscribe(subscription));
+        assert!(!bus.unsubscribe(subscription));
+        assert!(bus.is_empty());
+
+        the_goose
+            .execute(Command::Move("Pluto", [1, 1]), &mut bus)
+            .unwrap();
+        assert_eq!(*count.borrow(), 2);
+    }
+
+    #[test]
+    fn test_channel() {
+        let mut the_goose = the_goose();
+        let mut bus = SampleBus::new();
+
+        let (_, receiver) = bus.channel(Filter::all().kinds([EventKind::Roll]), 2);
+        let (full, overflow) = bus.channel(Filter::all(), 2);
+
+        bus.begin().unwrap();
+        bus.notify(Event::Roll("Pippo", [4, 1])).unwrap();
+        bus.rollback().unwrap();
+        assert_eq!(receiver.try_iter().count(), 0);
+        assert_eq!(overflow.try_iter().count(), 0);
+
+        assert_eq!(
+            the_goose.execute(Command::Move("Pippo", [4, 1]), &mut bus),
+            Ok(())
+        );
+        assert_eq!(the_goose.get("Pippo"), Some(&SamplePosition(10)));
+        assert_eq!(bus.len(), 2);
+        assert_eq!(bus.dropped(full), 2);
+        assert_eq!(overflow.try_iter().count(), 2);
+
+        the_goose
+            .execute(Command::Move("Pluto", [1, 1]), &mut bus)
+            .unwrap();
+
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<_>>(),
+            vec![Event::Roll("Pippo", [4, 1]), Event::Roll("Pluto", [1, 1])]
+        );
+
+        drop(receiver);
+        the_goose
+            .execute(Command::Move("Pippo", [1, 1]), &mut bus)
+            .unwrap();
+        assert_eq!(bus.len(), 1);
+        assert_eq!(bus.dropped(full), 4);
+
+        drop(overflow);
+        the_goose
+            .execute(Command::Move("Pluto", [1, 1]), &mut bus)
+            .unwrap();
+        assert!(bus.is_empty());
+        assert_eq!(bus.dropped(full), 0);
+    }
+}
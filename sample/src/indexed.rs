@@ -0,0 +1,414 @@
+use std::collections::{BTreeSet, HashMap};
+use std::hash::Hash;
+use std::ops::Bound;
+use std::sync::Arc;
+
+use crate::{SampleDie, SamplePosition, State, TheGoose, Turn};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Players<Player>(Arc<BTreeSet<Player>>);
+
+impl<Player: Ord> FromIterator<Player> for Players<Player> {
+    fn from_iter<T: IntoIterator<Item = Player>>(iter: T) -> Self {
+        Players(Arc::new(iter.into_iter().collect()))
+    }
+}
+
+impl<Player: Ord + Clone> IntoIterator for Players<Player> {
+    type Item = Player;
+    type IntoIter = PlayersIter<Player>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        PlayersIter {
+            players: self.0,
+            last: None,
+        }
+    }
+}
+
+pub struct PlayersIter<Player> {
+    players: Arc<BTreeSet<Player>>,
+    last: Option<Player>,
+}
+
+impl<Player: Ord + Clone> Iterator for PlayersIter<Player> {
+    type Item = Player;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = match &self.last {
+            None => self.players.iter().next(),
+            Some(last) => self
+                .players
+                .range((Bound::Excluded(last), Bound::Unbounded))
+                .next(),
+        }
+        .cloned();
+
+        self.last.clone_from(&next);
+
+        next
+    }
+}
+
+type Journal<Player> = (Vec<(Player, Option<SamplePosition>)>, Turn<Player>);
+
+pub struct IndexedTheGoose<Player> {
+    positions: HashMap<Player, SamplePosition>,
+    players: Arc<BTreeSet<Player>>,
+    squares: HashMap<SamplePosition, Arc<BTreeSet<Player>>>,
+    empty: Arc<BTreeSet<Player>>,
+    die: SampleDie,
+    journal: Option<Journal<Player>>,
+    turn: Turn<Player>,
+}
+
+impl<Player> IndexedTheGoose<Player> {
+    pub fn new() -> Self {
+        IndexedTheGoose::with_die(SampleDie::default())
+    }
+
+    pub fn with_die(die: SampleDie) -> Self {
+        IndexedTheGoose {
+            positions: HashMap::new(),
+            players: Arc::default(),
+            squares: HashMap::new(),
+            empty: Arc::default(),
+            die,
+            journal: None,
+            turn: Turn::default(),
+        }
+    }
+}
+
+impl<Player> Default for IndexedTheGoose<Player> {
+    fn default() -> Self {
+        IndexedTheGoose::new()
+    }
+}
+
+impl<Player: Eq + Hash + Ord + Clone> IndexedTheGoose<Player> {
+    fn leave(&mut self, player: &Player, position: &SamplePosition) {
+        if let Some(square) = self.squares.get_mut(position) {
+            Arc::make_mut(square).remove(player);
+            if square.is_empty() {
+                self.squares.remove(position);
+            }
+        }
+    }
+
+    fn enter(&mut self, player: Player, position: SamplePosition) {
+        Arc::make_mut(self.squares.entry(position).or_default()).insert(player);
+    }
+
+    fn record(&mut self, player: &Player) {
+        if let Some((journal, _)) = &mut self.journal {
+            journal.push((player.clone(), self.positions.get(player).copied()));
+        }
+    }
+
+    fn restore(&mut self, player: Player, position: Option<SamplePosition>) {
+        match (self.positions.remove(&player), position) {
+            (Some(previous), None) => {
+                Arc::make_mut(&mut self.players).remove(&player);
+                self.leave(&player, &previous);
+            }
+            (Some(previous), Some(position)) => {
+                self.leave(&player, &previous);
+                self.positions.insert(player.clone(), position);
+                self.enter(player, position);
+            }
+            (None, Some(position)) => {
+                Arc::make_mut(&mut self.players).insert(player.clone());
+                self.positions.insert(player.clone(), position);
+                self.enter(player, position);
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+impl<Player> State<Player, SamplePosition, Players<Player>> for IndexedTheGoose<Player>
+where
+    Player: Eq + Hash + Ord + Clone,
+{
+    type Error = std::convert::Infallible;
+
+    fn get_player_position(&self, player: &Player) -> Result<Option<SamplePosition>, Self::Error> {
+        Ok(self.positions.get(player).copied())
+    }
+
+    fn add_player(&mut self, player: Player) -> Result<(), Self::Error> {
+        let position = SamplePosition::from(0);
+
+        self.record(&player);
+        if let Some(previous) = self.positions.insert(player.clone(), position) {
+            self.leave(&player, &previous);
+        }
+        Arc::make_mut(&mut self.players).insert(player.clone());
+        self.enter(player, position);
+
+        Ok(())
+    }
+
+    fn remove_player(&mut self, player: &Player) -> Result<(), Self::Error> {
+        self.record(player);
+        if let Some(position) = self.positions.remove(player) {
+            Arc::make_mut(&mut self.players).remove(player);
+            self.leave(player, &position);
+        }
+
+        Ok(())
+    }
+
+    fn find_players_by_position(
+        &self,
+        position: &SamplePosition,
+    ) -> Result<Players<Player>, Self::Error> {
+        Ok(Players(
+            self.squares.get(position).unwrap_or(&self.empty).clone(),
+        ))
+    }
+
+    fn players(&self) -> Result<Players<Player>, Self::Error> {
+        Ok(Players(self.players.clone()))
+    }
+
+    fn update_player_position(
+        &mut self,
+        player: &Player,
+        position: &SamplePosition,
+    ) -> Result<(), Self::Error> {
+        self.record(player);
+        if let Some(previous) = self.positions.get_mut(player) {
+            let previous = std::mem::replace(previous, *position);
+            if previous != *position {
+                self.leave(player, &previous);
+                self.enter(player.clone(), *position);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn begin(&mut self) -> Result<(), Self::Error> {
+        self.journal = Some((Vec::new(), self.turn.clone()));
+
+        Ok(())
+    }
+
+    fn commit(&mut self) -> Result<(), Self::Error> {
+        self.journal = None;
+
+        Ok(())
+    }
+
+    fn rollback(&mut self) -> Result<(), Self::Error> {
+        if let Some((journal, turn)) = self.journal.take() {
+            for (player, position) in journal.into_iter().rev() {
+                self.restore(player, position);
+            }
+            self.turn = turn;
+        }
+
+        Ok(())
+    }
+
+    fn turn(&self) -> Result<Turn<Player>, Self::Error> {
+        Ok(self.turn.clone())
+    }
+
+    fn update_turn(&mut self, turn: Turn<Player>) -> Result<(), Self::Error> {
+        self.turn = turn;
+
+        Ok(())
+    }
+}
+
+impl<Player> TheGoose<Player, SamplePosition, [u32; 2], Players<Player>> for IndexedTheGoose<Player>
+where
+    Player: Eq + Hash + Ord + Clone,
+{
+    type State = Self;
+
+    fn state(&self) -> &Self::State {
+        self
+    }
+
+    fn state_mut(&mut self) -> &mut Self::State {
+        self
+    }
+
+    fn roll_dice(&mut self) -> [u32; 2] {
+        [self.die.roll(), self.die.roll()]
+    }
+
+    fn is_valid_face(&self, face: &u32) -> bool {
+        self.die.contains(face)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::{landing, Closed};
+    use crate::{collect, Cause, Command, Error, Event, PositionType, SampleTheGoose};
+
+    #[test]
+    fn test_players() {
+        let mut the_goose = IndexedTheGoose::new();
+
+        assert_eq!(
+            collect(&mut the_goose, Command::Add("Pippo")),
+            Ok(vec![Event::PlayerJoined("Pippo")].into())
+        );
+        assert_eq!(
+            collect(&mut the_goose, Command::Add("Pluto")),
+            Ok(vec![Event::PlayerJoined("Pluto")].into())
+        );
+        assert_eq!(
+            collect(&mut the_goose, Command::Remove("Pippo")),
+            Ok(vec![Event::PlayerLeft("Pippo")].into())
+        );
+
+        assert_eq!(
+            the_goose.players().unwrap().into_iter().collect::<Vec<_>>(),
+            vec!["Pluto"]
+        );
+    }
+
+    #[test]
+    fn test_index() {
+        let mut the_goose = IndexedTheGoose::new();
+
+        collect(&mut the_goose, Command::Add("Pippo")).ok();
+        collect(&mut the_goose, Command::Add("Pluto")).ok();
+        collect(&mut the_goose, Command::Add("Paperino")).ok();
+
+        let start = the_goose
+            .find_players_by_position(&SamplePosition(0))
+            .unwrap();
+
+        collect(&mut the_goose, Command::Move("Pippo", [3, 4])).ok();
+        collect(&mut the_goose, Command::Move("Pluto", [5, 6])).ok();
+
+        assert_eq!(
+            start.into_iter().collect::<Vec<_>>(),
+            vec!["Paperino", "Pippo", "Pluto"]
+        );
+
+        assert_eq!(
+            collect(&mut the_goose, Command::Move("Paperino", [6, 1])),
+            Ok(vec![
+                Event::Roll("Paperino", [6, 1]),
+                Event::Moved(
+                    "Paperino",
+                    SamplePosition(0),
+                    SamplePosition(7),
+                    landing(PositionType::Normal, Cause::Roll, 0)
+                ),
+                Event::Prank("Pippo", SamplePosition(7), SamplePosition(0))
+            ]
+            .into())
+        );
+
+        for (position, players) in [
+            (0, vec!["Pippo"]),
+            (7, vec!["Paperino"]),
+            (11, vec!["Pluto"]),
+            (2, vec![]),
+        ] {
+            assert_eq!(
+                the_goose
+                    .find_players_by_position(&SamplePosition(position))
+                    .unwrap()
+                    .into_iter()
+                    .collect::<Vec<_>>(),
+                players
+            );
+        }
+
+        collect(&mut the_goose, Command::Remove("Pippo")).ok();
+        assert!(!the_goose.squares.contains_key(&SamplePosition(0)));
+    }
+
+    #[test]
+    fn test_rollback() {
+        let mut the_goose = IndexedTheGoose::new();
+
+        collect(&mut the_goose, Command::Add("Pippo")).ok();
+        collect(&mut the_goose, Command::Add("Pluto")).ok();
+        the_goose
+            .update_player_position(&"Pluto", &SamplePosition(10))
+            .ok();
+
+        assert_eq!(
+            the_goose.execute(Command::Move("Pippo", [4, 1]), &mut Closed(3)),
+            Err(Error::Events("closed"))
+        );
+        assert_eq!(
+            the_goose.execute(Command::Add("Paperino"), &mut Closed(0)),
+            Err(Error::Events("closed"))
+        );
+        assert_eq!(
+            the_goose.execute(Command::Remove("Pluto"), &mut Closed(0)),
+            Err(Error::Events("closed"))
+        );
+
+        assert_eq!(
+            the_goose.players().unwrap().into_iter().collect::<Vec<_>>(),
+            vec!["Pippo", "Pluto"]
+        );
+        for (position, players) in [(0, vec!["Pippo"]), (5, vec![]), (10, vec!["Pluto"])] {
+            assert_eq!(
+                the_goose
+                    .find_players_by_position(&SamplePosition(position))
+                    .unwrap()
+                    .into_iter()
+                    .collect::<Vec<_>>(),
+                players
+            );
+        }
+        assert_eq!(the_goose.squares.len(), 2);
+    }
+
+    #[test]
+    fn test_same_game_as_sample() {
+        let mut indexed = IndexedTheGoose::with_die(SampleDie::new(1..=6, 42));
+        let mut sample = SampleTheGoose::<_, SamplePosition>::with_die(SampleDie::new(1..=6, 42));
+
+        let players = (0..40).collect::<Vec<u32>>();
+        for player in &players {
+            collect(&mut indexed, Command::Add(*player)).ok();
+            collect(&mut sample, Command::Add(*player)).ok();
+        }
+
+        for _ in 0..30 {
+            for player in &players {
+                let indexed = collect(&mut indexed, Command::RollAndMove(*player)).unwrap();
+                let sample = collect(&mut sample, Command::RollAndMove(*player)).unwrap();
+
+                assert_eq!(indexed.len(), sample.len());
+            }
+        }
+
+        for player in &players {
+            assert_eq!(
+                indexed.get_player_position(player),
+                sample.get_player_position(player)
+            );
+        }
+        for position in 0..=63 {
+            assert_eq!(
+                indexed
+                    .find_players_by_position(&SamplePosition(position))
+                    .unwrap()
+                    .into_iter()
+                    .collect::<Vec<_>>(),
+                sample
+                    .find_players_by_position(&SamplePosition(position))
+                    .unwrap()
+            );
+        }
+        assert_eq!(indexed.turn(), sample.turn());
+    }
+}
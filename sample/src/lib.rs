@@ -1,32 +1,122 @@
 pub use the_goose::*;
 
+pub mod asynch;
+pub mod bus;
+pub mod indexed;
+pub mod locale;
+pub mod notation;
+pub mod query;
+pub mod replay;
+pub mod svg;
+pub mod text;
+
 use std::collections::BTreeMap;
-use std::ops::{Deref, DerefMut};
+use std::ops::{Deref, DerefMut, RangeInclusive};
 
 #[derive(PartialEq, Debug)]
-pub struct SampleEvents<Player, Position, Roll, II: IntoIterator<Item = Player>>(
-    Vec<Event<Player, Position, Roll, II>>,
-);
+pub struct SampleEvents<Player, Position, Roll>(Vec<Event<Player, Position, Roll>>);
 
-impl<Player, Position, Roll, II: IntoIterator<Item = Player>> Deref
-    for SampleEvents<Player, Position, Roll, II>
-{
-    type Target = Vec<Event<Player, Position, Roll, II>>;
+impl<Player, Position, Roll> Deref for SampleEvents<Player, Position, Roll> {
+    type Target = Vec<Event<Player, Position, Roll>>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SamplePosition(u32);
 
-#[derive(Default)]
-pub struct SampleTheGoose<Player, Position>(BTreeMap<Player, Position>);
+#[derive(Clone, PartialEq, Debug)]
+pub struct SampleDie {
+    faces: RangeInclusive<u32>,
+    seed: u64,
+}
+
+impl SampleDie {
+    pub fn new(faces: RangeInclusive<u32>, seed: u64) -> Self {
+        assert!(!faces.is_empty(), "a die needs at least one face");
+
+        SampleDie { faces, seed }
+    }
+
+    pub fn faces(&self) -> &RangeInclusive<u32> {
+        &self.faces
+    }
+
+    /// Whether `face` is one of the die's faces and no longer than the board.
+    pub fn contains(&self, face: &u32) -> bool {
+        self.faces.contains(face) && *face <= SamplePosition::end().0
+    }
+
+    pub fn roll(&mut self) -> u32 {
+        // splitmix64
+        self.seed = self.seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.seed;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+
+        let (first, last) = (*self.faces.start(), *self.faces.end());
+        first + (z % (u64::from(last - first) + 1)) as u32
+    }
+}
+
+impl Default for SampleDie {
+    fn default() -> Self {
+        SampleDie::new(1..=6, 0)
+    }
+}
+
+type Journal<Player, Position> = (Vec<(Player, Option<Position>)>, Turn<Player>);
+
+#[derive(Clone, Default)]
+pub struct SampleTheGoose<Player, Position> {
+    positions: BTreeMap<Player, Position>,
+    die: SampleDie,
+    journal: Option<Journal<Player, Position>>,
+    goose_rule: GooseRule,
+    turn: Turn<Player>,
+    extra_turns: ExtraTurns,
+}
 
 impl<Player, Position> SampleTheGoose<Player, Position> {
     pub fn new() -> Self {
-        SampleTheGoose(BTreeMap::new())
+        SampleTheGoose::with_die(SampleDie::default())
+    }
+
+    pub fn with_die(die: SampleDie) -> Self {
+        SampleTheGoose {
+            positions: BTreeMap::new(),
+            die,
+            journal: None,
+            goose_rule: GooseRule::default(),
+            turn: Turn::default(),
+            extra_turns: ExtraTurns::default(),
+        }
+    }
+
+    pub fn with_goose_rule(mut self, goose_rule: GooseRule) -> Self {
+        self.goose_rule = goose_rule;
+        self
+    }
+
+    pub fn with_extra_turns(mut self, extra_turns: ExtraTurns) -> Self {
+        self.extra_turns = extra_turns;
+        self
+    }
+
+    pub fn die(&self) -> &SampleDie {
+        &self.die
+    }
+}
+
+impl<Player: Ord + Clone, Position: Copy> SampleTheGoose<Player, Position> {
+    fn record(&mut self, player: &Player) {
+        if let Some((journal, _)) = &mut self.journal {
+            journal.push((player.clone(), self.positions.get(player).copied()));
+        }
     }
 }
 
@@ -34,39 +124,59 @@ impl<Player, Position> Deref for SampleTheGoose<Player, Position> {
     type Target = BTreeMap<Player, Position>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.positions
     }
 }
 
 impl<Player, Position> DerefMut for SampleTheGoose<Player, Position> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.positions
     }
 }
 
-impl<Player, Position, Roll, II: IntoIterator<Item = Player>> Default
-    for SampleEvents<Player, Position, Roll, II>
-{
+impl<Player, Position, Roll> Default for SampleEvents<Player, Position, Roll> {
     fn default() -> Self {
         Self(Vec::default())
     }
 }
 
-impl<Player, Position, Roll, II: IntoIterator<Item = Player>> Events<Player, Position, Roll, II>
-    for SampleEvents<Player, Position, Roll, II>
+#[allow(clippy::type_complexity)]
+pub fn collect<Player, P, R, II, G>(
+    the_goose: &mut G,
+    command: Command<Player, R>,
+) -> Result<
+    SampleEvents<Player, P, R>,
+    Error<Player, R::Face, <G::State as State<Player, P, II>>::Error, std::convert::Infallible>,
+>
+where
+    G: TheGoose<Player, P, R, II>,
+    R: Roll,
+    II: IntoIterator<Item = Player>,
+    Player: Clone + PartialEq,
+    R::Face: Clone + PartialEq,
+    R::Step: Copy,
+    P: Position<R::Step> + Copy,
+{
+    let mut events = SampleEvents::default();
+
+    the_goose.execute(command, &mut events).map(|()| events)
+}
+
+impl<Player, Position, Roll> Events<Player, Position, Roll>
+    for SampleEvents<Player, Position, Roll>
 {
     type Error = std::convert::Infallible;
 
-    fn notify(&mut self, event: Event<Player, Position, Roll, II>) -> Result<(), Self::Error> {
+    fn notify(&mut self, event: Event<Player, Position, Roll>) -> Result<(), Self::Error> {
         self.0.push(event);
         Ok(())
     }
 }
 
-impl<Player, Position, Roll, II: IntoIterator<Item = Player>>
-    From<Vec<Event<Player, Position, Roll, II>>> for SampleEvents<Player, Position, Roll, II>
+impl<Player, Position, Roll> From<Vec<Event<Player, Position, Roll>>>
+    for SampleEvents<Player, Position, Roll>
 {
-    fn from(v: Vec<Event<Player, Position, Roll, II>>) -> Self {
+    fn from(v: Vec<Event<Player, Position, Roll>>) -> Self {
         Self(v)
     }
 }
@@ -74,7 +184,10 @@ impl<Player, Position, Roll, II: IntoIterator<Item = Player>>
 impl Position<u32> for SamplePosition {
     fn add(self, r: u32) -> AddPosition<Self> {
         if self.0 + r > 63 {
-            AddPosition::Bounced(SamplePosition(63 * 2 - self.0 - r + 1), SamplePosition(63))
+            AddPosition::Bounced(
+                SamplePosition((63 * 2u32).saturating_sub(self.0 + r)),
+                SamplePosition(63),
+            )
         } else {
             AddPosition::Normal(SamplePosition(self.0 + r))
         }
@@ -88,6 +201,20 @@ impl Position<u32> for SamplePosition {
             _ => PositionType::Normal,
         }
     }
+
+    fn end() -> Self {
+        SamplePosition(63)
+    }
+
+    fn next_goose(&self) -> Option<Self> {
+        (self.0 + 1..63)
+            .map(SamplePosition)
+            .find(|position| matches!(position.get_type(), PositionType::TheGoose))
+    }
+
+    fn is_dice(&self) -> bool {
+        matches!(self.0, 26 | 53)
+    }
 }
 
 impl std::convert::From<u32> for SamplePosition {
@@ -96,6 +223,12 @@ impl std::convert::From<u32> for SamplePosition {
     }
 }
 
+impl std::convert::From<SamplePosition> for u32 {
+    fn from(value: SamplePosition) -> Self {
+        value.0
+    }
+}
+
 impl<Player> State<Player, SamplePosition, Vec<Player>> for SampleTheGoose<Player, SamplePosition>
 where
     Player: Eq + std::hash::Hash + Clone + std::cmp::Ord,
@@ -107,12 +240,14 @@ where
     }
 
     fn add_player(&mut self, player: Player) -> Result<(), Self::Error> {
+        self.record(&player);
         self.insert(player, SamplePosition::from(0));
 
         Ok(())
     }
 
     fn remove_player(&mut self, player: &Player) -> Result<(), Self::Error> {
+        self.record(player);
         self.remove(player);
 
         Ok(())
@@ -138,21 +273,57 @@ where
         player: &Player,
         position: &SamplePosition,
     ) -> Result<(), Self::Error> {
+        self.record(player);
         if let Some(p) = self.get_mut(player) {
             *p = *position;
         }
 
         Ok(())
     }
+
+    fn begin(&mut self) -> Result<(), Self::Error> {
+        self.journal = Some((Vec::new(), self.turn.clone()));
+
+        Ok(())
+    }
+
+    fn commit(&mut self) -> Result<(), Self::Error> {
+        self.journal = None;
+
+        Ok(())
+    }
+
+    fn rollback(&mut self) -> Result<(), Self::Error> {
+        if let Some((journal, turn)) = self.journal.take() {
+            for (player, position) in journal.into_iter().rev() {
+                match position {
+                    Some(position) => self.insert(player, position),
+                    None => self.remove(&player),
+                };
+            }
+            self.turn = turn;
+        }
+
+        Ok(())
+    }
+
+    fn turn(&self) -> Result<Turn<Player>, Self::Error> {
+        Ok(self.turn.clone())
+    }
+
+    fn update_turn(&mut self, turn: Turn<Player>) -> Result<(), Self::Error> {
+        self.turn = turn;
+
+        Ok(())
+    }
 }
 
-impl<Player> TheGoose<Player, SamplePosition, u32, Vec<Player>>
+impl<Player> TheGoose<Player, SamplePosition, [u32; 2], Vec<Player>>
     for SampleTheGoose<Player, SamplePosition>
 where
     Player: Eq + std::hash::Hash + Clone + std::cmp::Ord,
 {
     type State = SampleTheGoose<Player, SamplePosition>;
-    type Events = SampleEvents<Player, SamplePosition, u32, Vec<Player>>;
 
     fn state(&self) -> &Self::State {
         self
@@ -162,30 +333,62 @@ where
         self
     }
 
-    fn roll_dice(&mut self) -> u32 {
-        0
+    fn roll_dice(&mut self) -> [u32; 2] {
+        [self.die.roll(), self.die.roll()]
+    }
+
+    fn is_valid_face(&self, face: &u32) -> bool {
+        self.die.contains(face)
+    }
+
+    fn goose_rule(&self) -> GooseRule {
+        self.goose_rule
+    }
+
+    fn extra_turns(&self) -> ExtraTurns {
+        self.extra_turns
     }
 }
 
-pub struct SimpleTheGoose<'a, I> {
+pub struct SimpleTheGoose<'a, I, F = u32> {
     state: SampleTheGoose<&'a str, SamplePosition>,
     rolls: I,
+    die: RangeInclusive<F>,
 }
 
 impl<'a, I> SimpleTheGoose<'a, I> {
     pub fn new(rolls: I) -> Self {
+        SimpleTheGoose::with_die(rolls, 1..=6)
+    }
+}
+
+impl<'a, I, F> SimpleTheGoose<'a, I, F> {
+    pub fn with_die(rolls: I, die: RangeInclusive<F>) -> Self {
         SimpleTheGoose {
-            state: SampleTheGoose(BTreeMap::new()),
+            state: SampleTheGoose::new(),
             rolls,
+            die,
         }
     }
+
+    pub fn with_goose_rule(mut self, goose_rule: GooseRule) -> Self {
+        self.state = self.state.with_goose_rule(goose_rule);
+        self
+    }
+
+    pub fn with_extra_turns(mut self, extra_turns: ExtraTurns) -> Self {
+        self.state = self.state.with_extra_turns(extra_turns);
+        self
+    }
 }
 
-impl<'a, I: Iterator<Item = u32>> TheGoose<&'a str, SamplePosition, u32, Vec<&'a str>>
-    for SimpleTheGoose<'a, I>
+impl<'a, F, R, I> TheGoose<&'a str, SamplePosition, R, Vec<&'a str>> for SimpleTheGoose<'a, I, F>
+where
+    F: PartialOrd,
+    R: Roll<Step = u32, Face = F>,
+    I: Iterator<Item = R>,
 {
     type State = SampleTheGoose<&'a str, SamplePosition>;
-    type Events = SampleEvents<&'a str, SamplePosition, u32, Vec<&'a str>>;
 
     fn state(&self) -> &Self::State {
         &self.state
@@ -195,27 +398,76 @@ impl<'a, I: Iterator<Item = u32>> TheGoose<&'a str, SamplePosition, u32, Vec<&'a
         &mut self.state
     }
 
-    fn roll_dice(&mut self) -> u32 {
+    fn roll_dice(&mut self) -> R {
         self.rolls.next().unwrap()
     }
+
+    fn is_valid_face(&self, face: &F) -> bool {
+        self.die.contains(face)
+    }
+
+    fn goose_rule(&self) -> GooseRule {
+        self.state.goose_rule
+    }
+
+    fn extra_turns(&self) -> ExtraTurns {
+        self.state.extra_turns
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
+    pub(crate) fn landing(square: PositionType, cause: Cause, sequence: u32) -> Landing {
+        Landing {
+            square,
+            cause,
+            sequence,
+        }
+    }
+
+    pub(crate) struct Closed(pub usize);
+
+    impl<Player, Position, Roll> Events<Player, Position, Roll> for Closed {
+        type Error = &'static str;
+
+        fn notify(&mut self, _: Event<Player, Position, Roll>) -> Result<(), Self::Error> {
+            if self.0 == 0 {
+                return Err("closed");
+            }
+            self.0 -= 1;
+
+            Ok(())
+        }
+    }
+
+    struct Uncommitted;
+
+    impl<Player, Position, Roll> Events<Player, Position, Roll> for Uncommitted {
+        type Error = &'static str;
+
+        fn notify(&mut self, _: Event<Player, Position, Roll>) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn commit(&mut self) -> Result<(), Self::Error> {
+            Err("uncommitted")
+        }
+    }
+
     #[test]
     fn test_scenario_1_1() {
         let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
 
         assert_eq!(
-            the_goose.execute(Command::Add("Pippo")),
-            Ok(vec![Event::Players(vec!["Pippo"])].into())
+            collect(&mut the_goose, Command::Add("Pippo")),
+            Ok(vec![Event::PlayerJoined("Pippo")].into())
         );
 
         assert_eq!(
-            the_goose.execute(Command::Add("Pluto")),
-            Ok(vec![Event::Players(vec!["Pippo", "Pluto"])].into())
+            collect(&mut the_goose, Command::Add("Pluto")),
+            Ok(vec![Event::PlayerJoined("Pluto")].into())
         );
     }
 
@@ -223,10 +475,10 @@ mod test {
     fn test_scenario_1_2() {
         let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
 
-        the_goose.execute(Command::Add("Pippo")).ok();
+        collect(&mut the_goose, Command::Add("Pippo")).ok();
 
         assert_eq!(
-            the_goose.execute(Command::Add("Pippo")),
+            collect(&mut the_goose, Command::Add("Pippo")),
             Err(Error::DuplicatePlayer("Pippo"))
         );
     }
@@ -235,45 +487,182 @@ mod test {
     fn test_scenario_1_3() {
         let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
 
-        the_goose.execute(Command::Add("Pippo")).ok();
+        collect(&mut the_goose, Command::Add("Pippo")).ok();
 
         assert_eq!(
-            the_goose.execute(Command::Remove("Pippo")),
-            Ok(vec![Event::Players(Vec::new())].into())
+            collect(&mut the_goose, Command::Remove("Pippo")),
+            Ok(vec![Event::PlayerLeft("Pippo")].into())
         );
     }
 
+    #[test]
+    fn test_remove_unknown_player() {
+        let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
+
+        assert_eq!(
+            collect(&mut the_goose, Command::Remove("Pippo")),
+            Err(Error::PlayerNotFound("Pippo"))
+        );
+        assert_eq!(
+            collect(&mut the_goose, Command::Kick("Pippo")),
+            Err(Error::PlayerNotFound("Pippo"))
+        );
+        assert_eq!(
+            collect(&mut the_goose, Command::SetPosition("Pippo", 10)),
+            Err(Error::PlayerNotFound("Pippo"))
+        );
+    }
+
+    #[test]
+    fn test_rename() {
+        let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
+
+        collect(&mut the_goose, Command::Add("Pippo")).ok();
+        collect(&mut the_goose, Command::Add("Pluto")).ok();
+        collect(&mut the_goose, Command::Move("Pluto", [1, 2])).ok();
+
+        assert_eq!(
+            collect(&mut the_goose, Command::Rename("Pluto", "Paperino")),
+            Ok(vec![Event::PlayerRenamed("Pluto", "Paperino")].into())
+        );
+        assert_eq!(the_goose.get("Paperino"), Some(&SamplePosition(3)));
+        assert_eq!(the_goose.players(), Ok(vec!["Paperino", "Pippo"]));
+        assert_eq!(the_goose.turn().unwrap().player, Some("Pippo"));
+
+        collect(&mut the_goose, Command::Move("Pippo", [1, 2])).ok();
+        assert_eq!(the_goose.turn().unwrap().player, Some("Paperino"));
+        collect(&mut the_goose, Command::Rename("Paperino", "Pluto")).ok();
+        assert_eq!(the_goose.turn().unwrap().player, Some("Pluto"));
+
+        assert_eq!(
+            collect(&mut the_goose, Command::Rename("Pluto", "Pippo")),
+            Err(Error::DuplicatePlayer("Pippo"))
+        );
+        assert_eq!(
+            collect(&mut the_goose, Command::Rename("Paperino", "Topolino")),
+            Err(Error::PlayerNotFound("Paperino"))
+        );
+    }
+
+    #[test]
+    fn test_silent_commands() {
+        let mut the_goose = SimpleTheGoose::new(vec![[1u32, 1]].into_iter());
+
+        assert_eq!(the_goose.add_player("Pippo"), Ok(()));
+        assert_eq!(
+            the_goose.add_player("Pippo"),
+            Err(Error::DuplicatePlayer("Pippo"))
+        );
+        assert_eq!(the_goose.rename_player(&"Pippo", "Pluto"), Ok(()));
+        assert_eq!(
+            the_goose.set_player_position(&"Pluto", &SamplePosition(30)),
+            Ok(())
+        );
+        assert_eq!(the_goose.state.get("Pluto"), Some(&SamplePosition(30)));
+        assert_eq!(the_goose.reset(), Ok(()));
+        assert_eq!(the_goose.state.get("Pluto"), Some(&SamplePosition(0)));
+        assert_eq!(the_goose.remove_player(&"Pluto"), Ok(()));
+        assert_eq!(the_goose.state.get("Pluto"), None);
+    }
+
+    #[test]
+    fn test_admin_commands() {
+        let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
+
+        collect(&mut the_goose, Command::Add("Pippo")).ok();
+        collect(&mut the_goose, Command::Add("Pluto")).ok();
+
+        assert_eq!(
+            collect(&mut the_goose, Command::SetPosition("Pippo", 30)),
+            Ok(vec![Event::PositionSet("Pippo", SamplePosition(30))].into())
+        );
+        assert_eq!(
+            collect(&mut the_goose, Command::SetPosition("Pippo", 63)),
+            Ok(vec![Event::PositionSet("Pippo", SamplePosition(63))].into())
+        );
+        assert_eq!(
+            collect(&mut the_goose, Command::SetPosition("Pippo", 64)),
+            Err(Error::InvalidPosition("Pippo", 64))
+        );
+        assert_eq!(
+            collect(&mut the_goose, Command::SetPosition("Minnie", 64)),
+            Err(Error::PlayerNotFound("Minnie"))
+        );
+        assert_eq!(the_goose.get("Pippo"), Some(&SamplePosition(63)));
+        collect(&mut the_goose, Command::Move("Pluto", [1, 2])).ok();
+        assert_eq!(the_goose.turn().unwrap().player, Some("Pippo"));
+
+        assert_eq!(
+            collect(&mut the_goose, Command::Reset),
+            Ok(vec![Event::Reset].into())
+        );
+        assert_eq!(the_goose.get("Pippo"), Some(&SamplePosition(0)));
+        assert_eq!(the_goose.get("Pluto"), Some(&SamplePosition(0)));
+        assert_eq!(the_goose.turn().unwrap().player, Some("Pippo"));
+
+        assert_eq!(
+            collect(&mut the_goose, Command::Rematch),
+            Ok(vec![Event::Rematch].into())
+        );
+        assert_eq!(the_goose.turn(), Ok(Turn::default()));
+
+        assert_eq!(
+            collect(&mut the_goose, Command::Kick("Pluto")),
+            Ok(vec![Event::Kicked("Pluto")].into())
+        );
+        assert_eq!(the_goose.players(), Ok(vec!["Pippo"]));
+    }
+
     #[test]
     fn test_scenario_2_1() {
         let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
 
-        the_goose.execute(Command::Add("Pippo")).ok();
-        the_goose.execute(Command::Add("Pluto")).ok();
+        collect(&mut the_goose, Command::Add("Pippo")).ok();
+        collect(&mut the_goose, Command::Add("Pluto")).ok();
 
         assert_eq!(
-            the_goose.execute(Command::Move("Pippo", 4, 2)),
+            collect(&mut the_goose, Command::Move("Pippo", [4, 2])),
             Ok(vec![
-                Event::Roll("Pippo", 4, 2),
-                Event::Moved("Pippo", SamplePosition(0), SamplePosition(6)),
-                Event::Jump("Pippo", SamplePosition(12))
+                Event::Roll("Pippo", [4, 2]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(0),
+                    SamplePosition(6),
+                    landing(PositionType::TheBridge, Cause::Roll, 0)
+                ),
+                Event::Jump(
+                    "Pippo",
+                    SamplePosition(12),
+                    landing(PositionType::Normal, Cause::TheBridge, 1)
+                )
             ]
             .into())
         );
 
         assert_eq!(
-            the_goose.execute(Command::Move("Pluto", 2, 2)),
+            collect(&mut the_goose, Command::Move("Pluto", [2, 2])),
             Ok(vec![
-                Event::Roll("Pluto", 2, 2),
-                Event::Moved("Pluto", SamplePosition(0), SamplePosition(4))
+                Event::Roll("Pluto", [2, 2]),
+                Event::Moved(
+                    "Pluto",
+                    SamplePosition(0),
+                    SamplePosition(4),
+                    landing(PositionType::Normal, Cause::Roll, 0)
+                )
             ]
             .into())
         );
 
         assert_eq!(
-            the_goose.execute(Command::Move("Pippo", 2, 3)),
+            collect(&mut the_goose, Command::Move("Pippo", [2, 3])),
             Ok(vec![
-                Event::Roll("Pippo", 2, 3),
-                Event::Moved("Pippo", SamplePosition(12), SamplePosition(17))
+                Event::Roll("Pippo", [2, 3]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(12),
+                    SamplePosition(17),
+                    landing(PositionType::Normal, Cause::Roll, 0)
+                )
             ]
             .into())
         );
@@ -286,10 +675,15 @@ mod test {
         the_goose.insert("Pippo", SamplePosition(60));
 
         assert_eq!(
-            the_goose.execute(Command::Move("Pippo", 1, 2)),
+            collect(&mut the_goose, Command::Move("Pippo", [1, 2])),
             Ok(vec![
-                Event::Roll("Pippo", 1, 2),
-                Event::Moved("Pippo", SamplePosition(60), SamplePosition(63)),
+                Event::Roll("Pippo", [1, 2]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(60),
+                    SamplePosition(63),
+                    landing(PositionType::End, Cause::Roll, 0)
+                ),
                 Event::Win("Pippo")
             ]
             .into())
@@ -303,12 +697,17 @@ mod test {
         the_goose.insert("Pippo", SamplePosition(60));
 
         assert_eq!(
-            the_goose.execute(Command::Move("Pippo", 3, 2)),
+            collect(&mut the_goose, Command::Move("Pippo", [3, 2])),
             Ok(vec![
-                Event::Roll("Pippo", 3, 2),
-                Event::Moved("Pippo", SamplePosition(60), SamplePosition(63)),
+                Event::Roll("Pippo", [3, 2]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(60),
+                    SamplePosition(63),
+                    landing(PositionType::Normal, Cause::Roll, 0)
+                ),
                 Event::Bounced("Pippo"),
-                Event::Return("Pippo", SamplePosition(62))
+                Event::Return("Pippo", SamplePosition(61))
             ]
             .into())
         );
@@ -316,16 +715,25 @@ mod test {
 
     #[test]
     fn test_scenario_4_1() {
-        let mut the_goose = SimpleTheGoose::new(vec![1, 2].into_iter());
+        let mut the_goose = SimpleTheGoose::new(vec![[1u32, 2]].into_iter());
 
         the_goose.state.insert("Pippo", SamplePosition(3));
 
         assert_eq!(
-            the_goose.execute(Command::RollAndMove("Pippo")),
+            collect(&mut the_goose, Command::RollAndMove("Pippo")),
             Ok(vec![
-                Event::Roll("Pippo", 1, 2),
-                Event::Moved("Pippo", SamplePosition(3), SamplePosition(6)),
-                Event::Jump("Pippo", SamplePosition(12))
+                Event::Roll("Pippo", [1, 2]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(3),
+                    SamplePosition(6),
+                    landing(PositionType::TheBridge, Cause::Roll, 0)
+                ),
+                Event::Jump(
+                    "Pippo",
+                    SamplePosition(12),
+                    landing(PositionType::Normal, Cause::TheBridge, 1)
+                )
             ]
             .into())
         );
@@ -333,16 +741,25 @@ mod test {
 
     #[test]
     fn test_scenario_5_1() {
-        let mut the_goose = SimpleTheGoose::new(vec![1, 1].into_iter());
+        let mut the_goose = SimpleTheGoose::new(vec![[1u32, 1]].into_iter());
 
         the_goose.state.insert("Pippo", SamplePosition(4));
 
         assert_eq!(
-            the_goose.execute(Command::RollAndMove("Pippo")),
+            collect(&mut the_goose, Command::RollAndMove("Pippo")),
             Ok(vec![
-                Event::Roll("Pippo", 1, 1),
-                Event::Moved("Pippo", SamplePosition(4), SamplePosition(6)),
-                Event::Jump("Pippo", SamplePosition(12))
+                Event::Roll("Pippo", [1, 1]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(4),
+                    SamplePosition(6),
+                    landing(PositionType::TheBridge, Cause::Roll, 0)
+                ),
+                Event::Jump(
+                    "Pippo",
+                    SamplePosition(12),
+                    landing(PositionType::Normal, Cause::TheBridge, 1)
+                )
             ]
             .into())
         );
@@ -350,16 +767,26 @@ mod test {
 
     #[test]
     fn test_scenario_6_1() {
-        let mut the_goose = SimpleTheGoose::new(vec![1, 1].into_iter());
+        let mut the_goose = SimpleTheGoose::new(vec![[1u32, 1]].into_iter());
 
         the_goose.state.insert("Pippo", SamplePosition(3));
 
         assert_eq!(
-            the_goose.execute(Command::RollAndMove("Pippo")),
+            collect(&mut the_goose, Command::RollAndMove("Pippo")),
             Ok(vec![
-                Event::Roll("Pippo", 1, 1),
-                Event::Moved("Pippo", SamplePosition(3), SamplePosition(5)),
-                Event::MovedAgain("Pippo", SamplePosition(5), SamplePosition(7))
+                Event::Roll("Pippo", [1, 1]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(3),
+                    SamplePosition(5),
+                    landing(PositionType::TheGoose, Cause::Roll, 0)
+                ),
+                Event::MovedAgain(
+                    "Pippo",
+                    SamplePosition(5),
+                    SamplePosition(7),
+                    landing(PositionType::Normal, Cause::TheGoose, 1)
+                )
             ]
             .into())
         );
@@ -367,37 +794,492 @@ mod test {
 
     #[test]
     fn test_scenario_6_2() {
-        let mut the_goose = SimpleTheGoose::new(vec![2, 2].into_iter());
+        let mut the_goose = SimpleTheGoose::new(vec![[2u32, 2]].into_iter());
+
+        the_goose.state.insert("Pippo", SamplePosition(10));
+
+        assert_eq!(
+            collect(&mut the_goose, Command::RollAndMove("Pippo")),
+            Ok(vec![
+                Event::Roll("Pippo", [2, 2]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(10),
+                    SamplePosition(14),
+                    landing(PositionType::TheGoose, Cause::Roll, 0)
+                ),
+                Event::MovedAgain(
+                    "Pippo",
+                    SamplePosition(14),
+                    SamplePosition(18),
+                    landing(PositionType::TheGoose, Cause::TheGoose, 1)
+                ),
+                Event::MovedAgain(
+                    "Pippo",
+                    SamplePosition(18),
+                    SamplePosition(22),
+                    landing(PositionType::Normal, Cause::TheGoose, 2)
+                )
+            ]
+            .into())
+        );
+    }
+
+    #[test]
+    fn test_scenario_6_1_goose_to_goose() {
+        let mut the_goose = SimpleTheGoose::new(vec![[1u32, 1]].into_iter())
+            .with_goose_rule(GooseRule::GooseToGoose);
+
+        the_goose.state.insert("Pippo", SamplePosition(3));
+
+        assert_eq!(
+            collect(&mut the_goose, Command::RollAndMove("Pippo")),
+            Ok(vec![
+                Event::Roll("Pippo", [1, 1]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(3),
+                    SamplePosition(5),
+                    landing(PositionType::TheGoose, Cause::Roll, 0)
+                ),
+                Event::GooseToGoose(
+                    "Pippo",
+                    SamplePosition(5),
+                    SamplePosition(9),
+                    landing(PositionType::TheGoose, Cause::GooseToGoose, 1)
+                ),
+                Event::ExtraTurn("Pippo")
+            ]
+            .into())
+        );
+        assert_eq!(the_goose.state.get("Pippo"), Some(&SamplePosition(9)));
+    }
+
+    #[test]
+    fn test_scenario_6_2_goose_to_goose() {
+        let mut the_goose = SimpleTheGoose::new(vec![[2u32, 2]].into_iter())
+            .with_goose_rule(GooseRule::GooseToGoose);
 
         the_goose.state.insert("Pippo", SamplePosition(10));
+        the_goose.state.insert("Pluto", SamplePosition(18));
+
+        assert_eq!(
+            collect(&mut the_goose, Command::RollAndMove("Pippo")),
+            Ok(vec![
+                Event::Roll("Pippo", [2, 2]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(10),
+                    SamplePosition(14),
+                    landing(PositionType::TheGoose, Cause::Roll, 0)
+                ),
+                Event::GooseToGoose(
+                    "Pippo",
+                    SamplePosition(14),
+                    SamplePosition(18),
+                    landing(PositionType::TheGoose, Cause::GooseToGoose, 1)
+                ),
+                Event::Prank("Pluto", SamplePosition(18), SamplePosition(10)),
+                Event::ExtraTurn("Pippo")
+            ]
+            .into())
+        );
+        assert_eq!(the_goose.state.get("Pluto"), Some(&SamplePosition(10)));
+    }
+
+    #[test]
+    fn test_last_goose_rolls_again() {
+        let mut the_goose = SimpleTheGoose::new(vec![[1u32, 1]].into_iter())
+            .with_goose_rule(GooseRule::GooseToGoose);
+
+        the_goose.state.insert("Pippo", SamplePosition(25));
+
+        assert_eq!(
+            collect(&mut the_goose, Command::RollAndMove("Pippo")),
+            Ok(vec![
+                Event::Roll("Pippo", [1, 1]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(25),
+                    SamplePosition(27),
+                    landing(PositionType::TheGoose, Cause::Roll, 0)
+                ),
+                Event::ExtraTurn("Pippo")
+            ]
+            .into())
+        );
+        assert_eq!(the_goose.state.turn().unwrap().player, Some("Pippo"));
+    }
+
+    #[test]
+    fn test_extra_turns_on_doubles() {
+        let mut the_goose = SimpleTheGoose::new([[1u32, 1], [2, 2], [3, 3], [4, 4]].into_iter())
+            .with_extra_turns(ExtraTurns {
+                doubles: true,
+                ..ExtraTurns::default()
+            });
+
+        the_goose.state.insert("Pippo", SamplePosition(30));
+        the_goose.state.insert("Pluto", SamplePosition(0));
+
+        for (i, extra_turn) in [true, true, true, false].into_iter().enumerate() {
+            let events = collect(&mut the_goose, Command::RollAndMove("Pippo")).unwrap();
+
+            assert_eq!(
+                events.last() == Some(&Event::ExtraTurn("Pippo")),
+                extra_turn
+            );
+            assert_eq!(
+                the_goose.state.turn(),
+                Ok(Turn {
+                    player: Some(if extra_turn { "Pippo" } else { "Pluto" }),
+                    extra_turns: if extra_turn { i as u8 + 1 } else { 0 },
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_extra_turns_on_dice() {
+        let mut the_goose = SimpleTheGoose::new([[1u32, 2], [1, 2]].into_iter());
+
+        the_goose.state.insert("Pippo", SamplePosition(23));
+        the_goose.state.insert("Pluto", SamplePosition(50));
+        the_goose.state.insert("Topolino", SamplePosition(0));
+
+        assert_eq!(
+            collect(&mut the_goose, Command::RollAndMove("Pippo")),
+            Ok(vec![
+                Event::Roll("Pippo", [1, 2]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(23),
+                    SamplePosition(26),
+                    landing(PositionType::Normal, Cause::Roll, 0)
+                )
+            ]
+            .into())
+        );
+        assert_eq!(the_goose.state.turn().unwrap().player, Some("Pluto"));
 
+        the_goose = the_goose.with_extra_turns(ExtraTurns {
+            dice: true,
+            ..ExtraTurns::default()
+        });
         assert_eq!(
-            the_goose.execute(Command::RollAndMove("Pippo")),
+            collect(&mut the_goose, Command::RollAndMove("Pluto")),
             Ok(vec![
-                Event::Roll("Pippo", 2, 2),
-                Event::Moved("Pippo", SamplePosition(10), SamplePosition(14)),
-                Event::MovedAgain("Pippo", SamplePosition(14), SamplePosition(18)),
-                Event::MovedAgain("Pippo", SamplePosition(18), SamplePosition(22))
+                Event::Roll("Pluto", [1, 2]),
+                Event::Moved(
+                    "Pluto",
+                    SamplePosition(50),
+                    SamplePosition(53),
+                    landing(PositionType::Normal, Cause::Roll, 0)
+                ),
+                Event::ExtraTurn("Pluto")
             ]
             .into())
         );
     }
 
+    #[test]
+    fn test_turn_order() {
+        let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
+
+        for player in ["Pippo", "Pluto", "Paperino"] {
+            collect(&mut the_goose, Command::Add(player)).unwrap();
+        }
+        assert_eq!(the_goose.turn(), Ok(Turn::default()));
+
+        collect(&mut the_goose, Command::Move("Pippo", [1, 2])).unwrap();
+        assert_eq!(the_goose.turn().unwrap().player, Some("Pluto"));
+        collect(&mut the_goose, Command::Move("Pluto", [1, 2])).unwrap();
+        assert_eq!(the_goose.turn().unwrap().player, Some("Paperino"));
+        collect(&mut the_goose, Command::Move("Paperino", [1, 2])).unwrap();
+        assert_eq!(the_goose.turn().unwrap().player, Some("Pippo"));
+
+        assert_eq!(
+            collect(&mut the_goose, Command::Move("Pluto", [1, 2])),
+            Err(Error::NotYourTurn("Pluto"))
+        );
+        assert_eq!(the_goose.turn().unwrap().player, Some("Pippo"));
+
+        collect(&mut the_goose, Command::Remove("Pippo")).unwrap();
+        assert_eq!(the_goose.turn().unwrap().player, Some("Pluto"));
+        collect(&mut the_goose, Command::Remove("Pluto")).unwrap();
+        assert_eq!(the_goose.turn().unwrap().player, Some("Paperino"));
+        collect(&mut the_goose, Command::Remove("Paperino")).unwrap();
+        assert_eq!(the_goose.turn().unwrap().player, None);
+    }
+
     #[test]
     fn test_scenario_7_1() {
-        let mut the_goose = SimpleTheGoose::new(vec![1, 1].into_iter());
+        let mut the_goose = SimpleTheGoose::new(vec![[1u32, 1]].into_iter());
 
         the_goose.state.insert("Pippo", SamplePosition(15));
         the_goose.state.insert("Pluto", SamplePosition(17));
 
         assert_eq!(
-            the_goose.execute(Command::RollAndMove("Pippo")),
+            collect(&mut the_goose, Command::RollAndMove("Pippo")),
             Ok(vec![
-                Event::Roll("Pippo", 1, 1),
-                Event::Moved("Pippo", SamplePosition(15), SamplePosition(17)),
+                Event::Roll("Pippo", [1, 1]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(15),
+                    SamplePosition(17),
+                    landing(PositionType::Normal, Cause::Roll, 0)
+                ),
                 Event::Prank("Pluto", SamplePosition(17), SamplePosition(15))
             ]
             .into())
         );
     }
+
+    #[test]
+    fn test_single_die() {
+        let mut the_goose = SimpleTheGoose::new(vec![[4u32]].into_iter());
+
+        the_goose.state.insert("Pippo", SamplePosition(3));
+
+        assert_eq!(
+            collect(&mut the_goose, Command::RollAndMove("Pippo")),
+            Ok(vec![
+                Event::Roll("Pippo", [4]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(3),
+                    SamplePosition(7),
+                    landing(PositionType::Normal, Cause::Roll, 0)
+                )
+            ]
+            .into())
+        );
+    }
+
+    #[test]
+    fn test_three_dice() {
+        let mut the_goose = SimpleTheGoose::new(vec![[1u32, 2, 4]].into_iter());
+
+        the_goose.state.insert("Pippo", SamplePosition(3));
+
+        assert_eq!(
+            collect(&mut the_goose, Command::RollAndMove("Pippo")),
+            Ok(vec![
+                Event::Roll("Pippo", [1, 2, 4]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(3),
+                    SamplePosition(10),
+                    landing(PositionType::Normal, Cause::Roll, 0)
+                )
+            ]
+            .into())
+        );
+    }
+
+    #[test]
+    fn test_special_faces() {
+        #[derive(PartialEq, PartialOrd, Clone, Debug)]
+        enum SpecialFace {
+            Skip,
+            Pips(u32),
+        }
+
+        impl Face for SpecialFace {
+            type Step = u32;
+
+            fn steps(&self) -> u32 {
+                match self {
+                    SpecialFace::Pips(n) => *n,
+                    SpecialFace::Skip => 0,
+                }
+            }
+        }
+
+        let mut the_goose = SimpleTheGoose::with_die(
+            vec![
+                [SpecialFace::Pips(3), SpecialFace::Skip],
+                [SpecialFace::Pips(7), SpecialFace::Skip],
+            ]
+            .into_iter(),
+            SpecialFace::Skip..=SpecialFace::Pips(6),
+        );
+
+        the_goose.state.insert("Pippo", SamplePosition(7));
+
+        assert_eq!(
+            collect(&mut the_goose, Command::RollAndMove("Pippo")),
+            Ok(vec![
+                Event::Roll("Pippo", [SpecialFace::Pips(3), SpecialFace::Skip]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(7),
+                    SamplePosition(10),
+                    landing(PositionType::Normal, Cause::Roll, 0)
+                )
+            ]
+            .into())
+        );
+
+        assert_eq!(
+            collect(&mut the_goose, Command::RollAndMove("Pippo")),
+            Err(Error::InvalidRoll("Pippo", SpecialFace::Pips(7)))
+        );
+
+        assert_eq!(
+            collect(
+                &mut the_goose,
+                Command::Move("Pippo", [SpecialFace::Skip, SpecialFace::Skip])
+            ),
+            Ok(vec![
+                Event::Roll("Pippo", [SpecialFace::Skip, SpecialFace::Skip]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(10),
+                    SamplePosition(10),
+                    landing(PositionType::Normal, Cause::Roll, 0)
+                )
+            ]
+            .into())
+        );
+        assert_eq!(the_goose.state.get("Pippo"), Some(&SamplePosition(10)));
+    }
+
+    #[test]
+    fn test_invalid_roll() {
+        let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
+
+        collect(&mut the_goose, Command::Add("Pippo")).ok();
+
+        assert_eq!(
+            collect(&mut the_goose, Command::Move("Pippo", [40, 0])),
+            Err(Error::InvalidRoll("Pippo", 40))
+        );
+
+        assert_eq!(
+            collect(&mut the_goose, Command::Move("Pippo", [3, 0])),
+            Err(Error::InvalidRoll("Pippo", 0))
+        );
+
+        assert_eq!(the_goose.get("Pippo"), Some(&SamplePosition(0)));
+    }
+
+    #[test]
+    fn test_sample_die() {
+        let mut die = SampleDie::default();
+
+        let rolls = (0..600).map(|_| die.roll()).collect::<Vec<_>>();
+
+        assert!(rolls.iter().all(|face| (1..=6).contains(face)));
+        assert!((1..=6).all(|face| rolls.contains(&face)));
+
+        let mut die = SampleDie::new(3..=3, 42);
+        assert_eq!(die.roll(), 3);
+
+        let die = SampleDie::new(1..=100, 42);
+        assert!(die.contains(&63));
+        assert!(!die.contains(&64));
+    }
+
+    #[test]
+    fn test_roll_and_move_with_sample_die() {
+        let mut the_goose = SampleTheGoose::<_, SamplePosition>::with_die(SampleDie::new(1..=1, 7));
+
+        collect(&mut the_goose, Command::Add("Pippo")).ok();
+
+        assert_eq!(
+            collect(&mut the_goose, Command::RollAndMove("Pippo")),
+            Ok(vec![
+                Event::Roll("Pippo", [1, 1]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(0),
+                    SamplePosition(2),
+                    landing(PositionType::Normal, Cause::Roll, 0)
+                )
+            ]
+            .into())
+        );
+    }
+
+    #[test]
+    fn test_events_errors() {
+        let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
+
+        assert_eq!(
+            the_goose.execute(Command::Add("Pippo"), &mut Closed(0)),
+            Err(Error::Events("closed"))
+        );
+        assert_eq!(the_goose.get("Pippo"), None);
+
+        collect(&mut the_goose, Command::Add("Pippo")).ok();
+        collect(&mut the_goose, Command::Add("Pluto")).ok();
+        the_goose.insert("Pluto", SamplePosition(10));
+
+        assert_eq!(
+            the_goose.execute(Command::Move("Pippo", [4, 1]), &mut Closed(3)),
+            Err(Error::Events("closed"))
+        );
+        assert_eq!(the_goose.get("Pippo"), Some(&SamplePosition(0)));
+        assert_eq!(the_goose.get("Pluto"), Some(&SamplePosition(10)));
+
+        assert_eq!(
+            the_goose.execute(Command::Remove("Pluto"), &mut Closed(0)),
+            Err(Error::Events("closed"))
+        );
+        assert_eq!(the_goose.get("Pluto"), Some(&SamplePosition(10)));
+
+        assert_eq!(
+            the_goose.execute(Command::Move("Pippo", [4, 1]), &mut Closed(4)),
+            Ok(())
+        );
+        assert_eq!(the_goose.get("Pippo"), Some(&SamplePosition(10)));
+        assert_eq!(the_goose.get("Pluto"), Some(&SamplePosition(0)));
+
+        assert_eq!(
+            the_goose.execute(Command::Move("Pluto", [1, 2]), &mut Uncommitted),
+            Err(Error::Events("uncommitted"))
+        );
+        assert_eq!(the_goose.get("Pluto"), Some(&SamplePosition(0)));
+        assert_eq!(the_goose.turn().unwrap().player, Some("Pluto"));
+    }
+
+    #[test]
+    fn test_preview_move() {
+        let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
+
+        the_goose.insert("Pippo", SamplePosition(3));
+        the_goose.insert("Pluto", SamplePosition(12));
+        the_goose.insert("Paperino", SamplePosition(14));
+
+        for (player, roll) in [("Pippo", [1, 2]), ("Pluto", [1, 1]), ("Paperino", [2, 2])] {
+            let before = the_goose.positions.clone();
+
+            let mut preview = SampleEvents::default();
+            the_goose.preview_move(&player, roll, &mut preview).unwrap();
+
+            assert_eq!(the_goose.positions, before);
+
+            assert_eq!(
+                collect(&mut the_goose, Command::Move(player, roll)),
+                Ok(preview)
+            );
+        }
+
+        let mut preview = SampleEvents::default();
+        the_goose
+            .preview_move(&"Pluto", [1, 2], &mut preview)
+            .unwrap();
+        assert_eq!(the_goose.turn().unwrap().player, Some("Pippo"));
+        assert_eq!(preview.len(), 2);
+
+        assert_eq!(
+            the_goose.preview_move(&"Pippo", [1, 2], &mut Uncommitted),
+            Err(Error::Events("uncommitted"))
+        );
+        assert_eq!(
+            the_goose.preview_move(&"Minnie", [1, 2], &mut SampleEvents::default()),
+            Err(Error::PlayerNotFound("Minnie"))
+        );
+    }
 }
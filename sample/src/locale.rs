@@ -0,0 +1,781 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::{Error, Event, Position, PositionType, SamplePosition};
+
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Locale {
+    #[default]
+    En,
+    It,
+    Es,
+    Fr,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Plural {
+    One,
+    Other,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Article {
+    None,
+    Masculine,
+    Feminine,
+    Elided,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Preposition {
+    To,
+    From,
+}
+
+pub struct Noun {
+    pub word: &'static str,
+    pub article: Article,
+}
+
+/// The messages of a locale. Templates interpolate `{name}` arguments, and places are
+/// rendered with the article of their noun, contracted with the preposition in front.
+/// Messages about players are phrased so they read the same whatever their gender.
+pub struct Catalog {
+    pub joined: &'static str,
+    pub left: &'static str,
+    pub renamed: &'static str,
+    pub roll: &'static str,
+    pub moved: &'static str,
+    pub moved_again: &'static str,
+    pub bounced: &'static str,
+    pub returned: &'static str,
+    pub win: &'static str,
+    /// Indexed by `Plural`, for one or more players sent back together.
+    pub prank: [&'static str; 2],
+    pub jump: &'static str,
+    pub goose_to_goose: &'static str,
+    pub extra_turn: &'static str,
+    pub position_set: &'static str,
+    pub kicked: &'static str,
+    pub reset: &'static str,
+    pub rematch: &'static str,
+    pub duplicate_player: &'static str,
+    pub player_not_found: &'static str,
+    pub invalid_roll: &'static str,
+    pub not_your_turn: &'static str,
+    pub invalid_position: &'static str,
+    pub error: &'static str,
+    pub start: Noun,
+    pub bridge: Noun,
+    pub goose: Noun,
+    pub and: &'static str,
+    /// Indexed by no preposition, `To` and `From`, then by a number and each `Article`.
+    pub articles: [[&'static str; 5]; 3],
+    pub plural: fn(usize) -> Plural,
+}
+
+fn one(n: usize) -> Plural {
+    if n == 1 {
+        Plural::One
+    } else {
+        Plural::Other
+    }
+}
+
+fn at_most_one(n: usize) -> Plural {
+    if n <= 1 {
+        Plural::One
+    } else {
+        Plural::Other
+    }
+}
+
+pub static EN: Catalog = Catalog {
+    joined: "{player} joins the game",
+    left: "{player} leaves the game",
+    renamed: "{from} is now called {player}",
+    roll: "{player} rolls {roll}",
+    moved: "{player} moves {from} {to}",
+    moved_again: "{player} moves again and goes {to}",
+    bounced: "{player} bounces!",
+    returned: "{player} returns {to}",
+    win: "{player} Wins!!",
+    prank: [
+        "On {on} there is {player}, who returns {to}",
+        "On {on} there are {player}, who return {to}",
+    ],
+    jump: "{player} jumps {to}",
+    goose_to_goose: "From goose to goose, {player} flies {to}",
+    extra_turn: "{player} rolls again",
+    position_set: "{player} is sent {to}",
+    kicked: "{player} is kicked out",
+    reset: "Everyone goes back {to}",
+    rematch: "Rematch!",
+    duplicate_player: "{player}: already existing player",
+    player_not_found: "{player}: unknown player",
+    invalid_roll: "{player}: invalid roll {face}",
+    not_your_turn: "{player}: not your turn",
+    invalid_position: "{player}: invalid position {position}",
+    error: "error: {error}",
+    start: Noun {
+        word: "Start",
+        article: Article::None,
+    },
+    bridge: Noun {
+        word: "The Bridge",
+        article: Article::None,
+    },
+    goose: Noun {
+        word: "The Goose",
+        article: Article::None,
+    },
+    and: "and",
+    articles: [
+        ["", "", "", "", ""],
+        ["to", "to", "to", "to", "to"],
+        ["from", "from", "from", "from", "from"],
+    ],
+    plural: one,
+};
+
+pub static IT: Catalog = Catalog {
+    joined: "{player} entra in gioco",
+    left: "{player} lascia il gioco",
+    renamed: "{from} ora si chiama {player}",
+    roll: "{player} tira {roll}",
+    moved: "{player} muove {from} {to}",
+    moved_again: "{player} muove di nuovo e va {to}",
+    bounced: "{player} rimbalza!",
+    returned: "{player} torna {to}",
+    win: "{player} Vince!!",
+    prank: [
+        "Su {on} c'è {player}, che torna {to}",
+        "Su {on} ci sono {player}, che tornano {to}",
+    ],
+    jump: "{player} salta {to}",
+    goose_to_goose: "Di oca in oca, {player} vola {to}",
+    extra_turn: "{player} tira ancora",
+    position_set: "{player} passa {to}",
+    kicked: "{player} è fuori dal gioco",
+    reset: "Tutti tornano {to}",
+    rematch: "Rivincita!",
+    duplicate_player: "{player}: giocatore già esistente",
+    player_not_found: "{player}: giocatore sconosciuto",
+    invalid_roll: "{player}: lancio non valido {face}",
+    not_your_turn: "{player}: non è il tuo turno",
+    invalid_position: "{player}: posizione non valida {position}",
+    error: "errore: {error}",
+    start: Noun {
+        word: "Partenza",
+        article: Article::Feminine,
+    },
+    bridge: Noun {
+        word: "Ponte",
+        article: Article::Masculine,
+    },
+    goose: Noun {
+        word: "Oca",
+        article: Article::Elided,
+    },
+    and: "e",
+    articles: [
+        ["", "", "il", "la", "l'"],
+        ["a", "a", "al", "alla", "all'"],
+        ["da", "da", "dal", "dalla", "dall'"],
+    ],
+    plural: one,
+};
+
+pub static ES: Catalog = Catalog {
+    joined: "{player} entra en el juego",
+    left: "{player} deja el juego",
+    renamed: "{from} ahora se llama {player}",
+    roll: "{player} tira {roll}",
+    moved: "{player} se mueve {from} {to}",
+    moved_again: "{player} se mueve otra vez y va {to}",
+    bounced: "¡{player} rebota!",
+    returned: "{player} vuelve {to}",
+    win: "¡¡{player} Gana!!",
+    prank: [
+        "En {on} está {player}, que vuelve {to}",
+        "En {on} están {player}, que vuelven {to}",
+    ],
+    jump: "{player} salta {to}",
+    goose_to_goose: "De oca a oca, {player} vuela {to}",
+    extra_turn: "{player} tira otra vez",
+    position_set: "Se envía a {player} {to}",
+    kicked: "Se expulsa a {player}",
+    reset: "Todos vuelven {to}",
+    rematch: "¡Revancha!",
+    duplicate_player: "{player}: jugador ya existente",
+    player_not_found: "{player}: jugador desconocido",
+    invalid_roll: "{player}: tirada no válida {face}",
+    not_your_turn: "{player}: no es tu turno",
+    invalid_position: "{player}: posición no válida {position}",
+    error: "error: {error}",
+    start: Noun {
+        word: "Salida",
+        article: Article::Feminine,
+    },
+    bridge: Noun {
+        word: "Puente",
+        article: Article::Masculine,
+    },
+    goose: Noun {
+        word: "Oca",
+        article: Article::Feminine,
+    },
+    and: "y",
+    articles: [
+        ["", "", "el", "la", "la"],
+        ["a", "a", "al", "a la", "a la"],
+        ["de", "de", "del", "de la", "de la"],
+    ],
+    plural: one,
+};
+
+pub static FR: Catalog = Catalog {
+    joined: "{player} rejoint la partie",
+    left: "{player} quitte la partie",
+    renamed: "{from} s'appelle maintenant {player}",
+    roll: "{player} lance {roll}",
+    moved: "{player} se déplace {from} {to}",
+    moved_again: "{player} se déplace encore et va {to}",
+    bounced: "{player} rebondit !",
+    returned: "{player} retourne {to}",
+    win: "{player} Gagne !!",
+    prank: [
+        "Sur {on} il y a {player}, qui retourne {to}",
+        "Sur {on} il y a {player}, qui retournent {to}",
+    ],
+    jump: "{player} saute {to}",
+    goose_to_goose: "D'oie en oie, {player} s'envole {to}",
+    extra_turn: "{player} rejoue",
+    position_set: "On envoie {player} {to}",
+    kicked: "On exclut {player}",
+    reset: "Tout le monde retourne {to}",
+    rematch: "Revanche !",
+    duplicate_player: "{player} : joueur déjà existant",
+    player_not_found: "{player} : joueur inconnu",
+    invalid_roll: "{player} : lancer invalide {face}",
+    not_your_turn: "{player} : ce n'est pas ton tour",
+    invalid_position: "{player} : position invalide {position}",
+    error: "erreur : {error}",
+    start: Noun {
+        word: "Départ",
+        article: Article::Masculine,
+    },
+    bridge: Noun {
+        word: "Pont",
+        article: Article::Masculine,
+    },
+    goose: Noun {
+        word: "Oie",
+        article: Article::Elided,
+    },
+    and: "et",
+    articles: [
+        ["", "", "le", "la", "l'"],
+        ["à", "à", "au", "à la", "à l'"],
+        ["de", "de", "du", "de la", "de l'"],
+    ],
+    plural: at_most_one,
+};
+
+fn join<T: Display>(items: impl IntoIterator<Item = T>) -> String {
+    items
+        .into_iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Replaces every `{name}` in `template` with the matching argument, leaving unknown
+/// names untouched.
+pub fn fill(template: &str, arguments: &[(&str, &dyn Display)]) -> String {
+    let mut output = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let argument = rest.find('}').and_then(|end| {
+            arguments
+                .iter()
+                .find(|(name, _)| *name == &rest[1..end])
+                .map(|(_, value)| (end, value))
+        });
+        match argument {
+            Some((end, value)) => {
+                output.push_str(&value.to_string());
+                rest = &rest[end + 1..];
+            }
+            None => {
+                output.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+
+    output
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+impl Catalog {
+    fn article(&self, preposition: Option<Preposition>, article: Option<Article>) -> &'static str {
+        let row = match preposition {
+            None => 0,
+            Some(Preposition::To) => 1,
+            Some(Preposition::From) => 2,
+        };
+        let column = match article {
+            None => 0,
+            Some(Article::None) => 1,
+            Some(Article::Masculine) => 2,
+            Some(Article::Feminine) => 3,
+            Some(Article::Elided) => 4,
+        };
+
+        self.articles[row][column]
+    }
+
+    pub fn noun(&self, preposition: Option<Preposition>, noun: &Noun) -> String {
+        let article = self.article(preposition, Some(noun.article));
+        let article = match preposition {
+            None => capitalize(article),
+            Some(_) => article.to_string(),
+        };
+
+        match article.as_str() {
+            "" => noun.word.to_string(),
+            a if a.ends_with('\'') => format!("{a}{}", noun.word),
+            a => format!("{a} {}", noun.word),
+        }
+    }
+
+    pub fn number(&self, preposition: Option<Preposition>, number: u32) -> String {
+        match self.article(preposition, None) {
+            "" => number.to_string(),
+            a => format!("{a} {number}"),
+        }
+    }
+
+    pub fn name(&self, preposition: Option<Preposition>, position: &SamplePosition) -> String {
+        match position.0 {
+            0 => self.noun(preposition, &self.start),
+            n => self.number(preposition, n),
+        }
+    }
+
+    pub fn destination(
+        &self,
+        preposition: Option<Preposition>,
+        position: &SamplePosition,
+    ) -> String {
+        match position.get_type() {
+            PositionType::TheBridge => self.noun(preposition, &self.bridge),
+            PositionType::TheGoose => format!(
+                "{}, {}",
+                self.name(preposition, position),
+                self.noun(None, &self.goose)
+            ),
+            PositionType::Normal | PositionType::End => self.name(preposition, position),
+        }
+    }
+
+    pub fn players<Player: Display>(&self, players: &[Player]) -> String {
+        match players {
+            [] => String::new(),
+            [player] => player.to_string(),
+            [rest @ .., last] => format!("{} {} {last}", join(rest), self.and),
+        }
+    }
+
+    pub fn prank<Player: Display>(
+        &self,
+        players: &[Player],
+        from: &SamplePosition,
+        to: &SamplePosition,
+    ) -> String {
+        let template = match (self.plural)(players.len()) {
+            Plural::One => self.prank[0],
+            Plural::Other => self.prank[1],
+        };
+
+        fill(
+            template,
+            &[
+                ("player", &self.players(players)),
+                ("on", &self.name(None, from)),
+                ("to", &self.name(Some(Preposition::To), to)),
+            ],
+        )
+    }
+
+    pub fn event<Player: Display, F: Display, const N: usize>(
+        &self,
+        event: &Event<Player, SamplePosition, [F; N]>,
+    ) -> String {
+        use Preposition::*;
+
+        match event {
+            Event::PlayerJoined(player) => fill(self.joined, &[("player", player)]),
+            Event::PlayerLeft(player) => fill(self.left, &[("player", player)]),
+            Event::PlayerRenamed(from, player) => {
+                fill(self.renamed, &[("from", from), ("player", player)])
+            }
+            Event::Roll(player, roll) => {
+                fill(self.roll, &[("player", player), ("roll", &join(roll))])
+            }
+            Event::Moved(player, from, to, _) => fill(
+                self.moved,
+                &[
+                    ("player", player),
+                    ("from", &self.name(Some(From), from)),
+                    ("to", &self.destination(Some(To), to)),
+                ],
+            ),
+            Event::MovedAgain(player, _, to, _) => fill(
+                self.moved_again,
+                &[("player", player), ("to", &self.destination(Some(To), to))],
+            ),
+            Event::Bounced(player) => fill(self.bounced, &[("player", player)]),
+            Event::Return(player, position) => fill(
+                self.returned,
+                &[("player", player), ("to", &self.name(Some(To), position))],
+            ),
+            Event::Win(player) => fill(self.win, &[("player", player)]),
+            Event::Prank(player, from, to) => self.prank(&[player], from, to),
+            Event::Jump(player, position, _) => fill(
+                self.jump,
+                &[("player", player), ("to", &self.name(Some(To), position))],
+            ),
+            Event::GooseToGoose(player, _, to, _) => fill(
+                self.goose_to_goose,
+                &[("player", player), ("to", &self.destination(Some(To), to))],
+            ),
+            Event::ExtraTurn(player) => fill(self.extra_turn, &[("player", player)]),
+            Event::PositionSet(player, position) => fill(
+                self.position_set,
+                &[("player", player), ("to", &self.name(Some(To), position))],
+            ),
+            Event::Kicked(player) => fill(self.kicked, &[("player", player)]),
+            Event::Reset => fill(
+                self.reset,
+                &[("to", &self.name(Some(To), &SamplePosition::from(0)))],
+            ),
+            Event::Rematch => self.rematch.to_string(),
+        }
+    }
+}
+
+impl Locale {
+    pub const ALL: [Locale; 4] = [Locale::En, Locale::It, Locale::Es, Locale::Fr];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::It => "it",
+            Locale::Es => "es",
+            Locale::Fr => "fr",
+        }
+    }
+
+    pub fn catalog(&self) -> &'static Catalog {
+        match self {
+            Locale::En => &EN,
+            Locale::It => &IT,
+            Locale::Es => &ES,
+            Locale::Fr => &FR,
+        }
+    }
+
+    pub fn render<Player: Display, F: Display, const N: usize>(
+        &self,
+        events: &[Event<Player, SamplePosition, [F; N]>],
+    ) -> String {
+        let mut output = String::new();
+        let mut separator = "";
+        let mut rest = events;
+
+        while let [event, tail @ ..] = rest {
+            output.push_str(separator);
+            separator = match event {
+                Event::Bounced(_) => " ",
+                _ => ". ",
+            };
+            rest = tail;
+
+            match event {
+                Event::Prank(player, from, to) => {
+                    let mut players = vec![player];
+                    while let [Event::Prank(next, f, t), tail @ ..] = rest {
+                        if (f, t) != (from, to) {
+                            break;
+                        }
+                        players.push(next);
+                        rest = tail;
+                    }
+                    output.push_str(&self.catalog().prank(&players, from, to));
+                }
+                _ => output.push_str(&self.catalog().event(event)),
+            }
+        }
+
+        output
+    }
+
+    pub fn render_error<Player: Display, F: Display, E: Display, V: Display>(
+        &self,
+        error: &Error<Player, F, E, V>,
+    ) -> String {
+        let catalog = self.catalog();
+
+        match error {
+            Error::Inner(e) => fill(catalog.error, &[("error", e)]),
+            Error::Events(e) => fill(catalog.error, &[("error", e)]),
+            Error::DuplicatePlayer(player) => fill(catalog.duplicate_player, &[("player", player)]),
+            Error::PlayerNotFound(player) => fill(catalog.player_not_found, &[("player", player)]),
+            Error::InvalidRoll(player, face) => {
+                fill(catalog.invalid_roll, &[("player", player), ("face", face)])
+            }
+            Error::NotYourTurn(player) => fill(catalog.not_your_turn, &[("player", player)]),
+            Error::InvalidPosition(player, position) => fill(
+                catalog.invalid_position,
+                &[("player", player), ("position", position)],
+            ),
+        }
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s.split(['-', '_']).next().unwrap_or_default();
+
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.code().eq_ignore_ascii_case(language))
+            .ok_or_else(|| format!("{s}: unsupported locale"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{collect, Command, SampleTheGoose, SimpleTheGoose, State, Turn};
+
+    fn all<Player: Display>(events: &[Event<Player, SamplePosition, [u32; 2]>]) -> Vec<String> {
+        Locale::ALL
+            .iter()
+            .map(|locale| locale.render(events))
+            .collect()
+    }
+
+    #[test]
+    fn test_fill() {
+        assert_eq!(
+            fill("{a} and {b}, {c} {", &[("a", &1), ("b", &"{a}")]),
+            "1 and {a}, {c} {"
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("it".parse(), Ok(Locale::It));
+        assert_eq!("es-AR".parse(), Ok(Locale::Es));
+        assert_eq!("FR_ca".parse(), Ok(Locale::Fr));
+        assert_eq!(
+            "de".parse::<Locale>(),
+            Err("de: unsupported locale".to_string())
+        );
+        assert_eq!(Locale::default().to_string(), "en");
+    }
+
+    #[test]
+    fn test_players() {
+        assert_eq!(
+            all::<&str>(&[
+                Event::PlayerJoined("Pippo"),
+                Event::PlayerRenamed("Pippo", "Pluto"),
+                Event::PlayerLeft("Pluto")
+            ]),
+            [
+                "Pippo joins the game. Pippo is now called Pluto. Pluto leaves the game",
+                "Pippo entra in gioco. Pippo ora si chiama Pluto. Pluto lascia il gioco",
+                "Pippo entra en el juego. Pippo ahora se llama Pluto. Pluto deja el juego",
+                "Pippo rejoint la partie. Pippo s'appelle maintenant Pluto. \
+                 Pluto quitte la partie",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pranks() {
+        let prank = |player| Event::Prank(player, SamplePosition(5), SamplePosition(3));
+
+        assert_eq!(
+            all(&[prank("Pluto")]),
+            [
+                "On 5 there is Pluto, who returns to 3",
+                "Su 5 c'è Pluto, che torna a 3",
+                "En 5 está Pluto, que vuelve a 3",
+                "Sur 5 il y a Pluto, qui retourne à 3",
+            ]
+        );
+        assert_eq!(
+            all(&[prank("Paperino"), prank("Pluto")]),
+            [
+                "On 5 there are Paperino and Pluto, who return to 3",
+                "Su 5 ci sono Paperino e Pluto, che tornano a 3",
+                "En 5 están Paperino y Pluto, que vuelven a 3",
+                "Sur 5 il y a Paperino et Pluto, qui retournent à 3",
+            ]
+        );
+        assert_eq!(
+            Locale::En.render(&[
+                prank("Minnie"),
+                prank("Paperino"),
+                prank("Pluto"),
+                Event::Prank("Pippo", SamplePosition(5), SamplePosition(0)),
+            ]),
+            "On 5 there are Minnie, Paperino and Pluto, who return to 3. \
+             On 5 there is Pippo, who returns to Start"
+        );
+    }
+
+    #[test]
+    fn test_admin() {
+        assert_eq!(
+            all::<&str>(&[
+                Event::PositionSet("Pippo", SamplePosition(5)),
+                Event::Kicked("Pluto")
+            ]),
+            [
+                "Pippo is sent to 5. Pluto is kicked out",
+                "Pippo passa a 5. Pluto è fuori dal gioco",
+                "Se envía a Pippo a 5. Se expulsa a Pluto",
+                "On envoie Pippo à 5. On exclut Pluto",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_places() {
+        let mut the_goose = SimpleTheGoose::new(vec![[1u32, 1], [2, 1], [1, 1]].into_iter());
+
+        the_goose.state.insert("Pippo", SamplePosition(4));
+        assert_eq!(
+            all(&collect(&mut the_goose, Command::RollAndMove("Pippo")).unwrap()),
+            [
+                "Pippo rolls 1, 1. Pippo moves from 4 to The Bridge. Pippo jumps to 12",
+                "Pippo tira 1, 1. Pippo muove da 4 al Ponte. Pippo salta a 12",
+                "Pippo tira 1, 1. Pippo se mueve de 4 al Puente. Pippo salta a 12",
+                "Pippo lance 1, 1. Pippo se déplace de 4 au Pont. Pippo saute à 12",
+            ]
+        );
+
+        the_goose.state.insert("Pippo", SamplePosition(0));
+        the_goose.state.insert("Pluto", SamplePosition(5));
+        assert_eq!(
+            all(&collect(&mut the_goose, Command::RollAndMove("Pippo")).unwrap())[1..],
+            [
+                "Pippo tira 2, 1. Pippo muove dalla Partenza a 3",
+                "Pippo tira 2, 1. Pippo se mueve de la Salida a 3",
+                "Pippo lance 2, 1. Pippo se déplace du Départ à 3",
+            ]
+        );
+
+        the_goose.state.insert("Pippo", SamplePosition(3));
+        the_goose.state.update_turn(Turn::default()).unwrap();
+        assert_eq!(
+            all(&collect(&mut the_goose, Command::RollAndMove("Pippo")).unwrap()),
+            [
+                "Pippo rolls 1, 1. Pippo moves from 3 to 5, The Goose. \
+                 On 5 there is Pluto, who returns to 3. Pippo moves again and goes to 7",
+                "Pippo tira 1, 1. Pippo muove da 3 a 5, L'Oca. \
+                 Su 5 c'è Pluto, che torna a 3. Pippo muove di nuovo e va a 7",
+                "Pippo tira 1, 1. Pippo se mueve de 3 a 5, La Oca. \
+                 En 5 está Pluto, que vuelve a 3. Pippo se mueve otra vez y va a 7",
+                "Pippo lance 1, 1. Pippo se déplace de 3 à 5, L'Oie. \
+                 Sur 5 il y a Pluto, qui retourne à 3. Pippo se déplace encore et va à 7",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bounce_and_win() {
+        let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
+        the_goose.insert("Pippo", SamplePosition(60));
+
+        let events = collect(&mut the_goose, Command::Move("Pippo", [3, 2])).unwrap();
+        assert_eq!(
+            all(&events[2..]),
+            [
+                "Pippo bounces! Pippo returns to 61",
+                "Pippo rimbalza! Pippo torna a 61",
+                "¡Pippo rebota! Pippo vuelve a 61",
+                "Pippo rebondit ! Pippo retourne à 61",
+            ]
+        );
+
+        the_goose.insert("Pippo", SamplePosition(60));
+        let events = collect(&mut the_goose, Command::Move("Pippo", [1, 2])).unwrap();
+        assert_eq!(
+            all(&events[2..]),
+            [
+                "Pippo Wins!!",
+                "Pippo Vince!!",
+                "¡¡Pippo Gana!!",
+                "Pippo Gagne !!"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
+        collect(&mut the_goose, Command::Add("Pippo")).unwrap();
+
+        let error = collect(&mut the_goose, Command::Add("Pippo")).unwrap_err();
+        assert_eq!(
+            Locale::ALL.map(|locale| locale.render_error(&error)),
+            [
+                "Pippo: already existing player",
+                "Pippo: giocatore già esistente",
+                "Pippo: jugador ya existente",
+                "Pippo : joueur déjà existant"
+            ]
+        );
+
+        let error = collect(&mut the_goose, Command::Move("Pippo", [2, 9])).unwrap_err();
+        assert_eq!(
+            Locale::It.render_error(&error),
+            "Pippo: lancio non valido 9"
+        );
+
+        assert_eq!(IT.noun(None, &IT.bridge), "Il Ponte");
+        assert_eq!(ES.noun(None, &ES.bridge), "El Puente");
+        assert_eq!(IT.noun(Some(Preposition::From), &IT.goose), "dall'Oca");
+        assert_eq!(FR.noun(Some(Preposition::To), &FR.goose), "à l'Oie");
+    }
+}
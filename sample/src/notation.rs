@@ -0,0 +1,575 @@
+use std::fmt::{self, Display, Write};
+
+use crate::replay::Log;
+use crate::text::render_error;
+use crate::{
+    collect, Cause, Command, Event, ExtraTurns, GooseRule, Landing, Position, SampleDie,
+    SamplePosition, SampleTheGoose,
+};
+
+pub const CLASSIC: &str = "classic";
+pub const SPANISH: &str = "spanish";
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Notation {
+    pub variant: String,
+    pub seed: u64,
+    pub log: Log<String, [u32; 2]>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum NotationError {
+    Syntax(usize),
+    UnknownVariant(String),
+    Rejected(usize, String),
+    Diverged(usize),
+}
+
+impl Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotationError::Syntax(line) => write!(f, "line {line}: syntax error"),
+            NotationError::UnknownVariant(variant) => write!(f, "{variant}: unknown variant"),
+            NotationError::Rejected(line, error) => write!(f, "line {line}: {error}"),
+            NotationError::Diverged(line) => {
+                write!(f, "line {line}: the recorded move does not match the game")
+            }
+        }
+    }
+}
+
+type GameEvent = Event<String, SamplePosition, [u32; 2]>;
+
+fn write_move(output: &mut String, player: &str, events: &[GameEvent]) {
+    write!(output, "{player}").expect("writing to a string");
+
+    for event in events {
+        match event {
+            Event::Roll(_, [first, second]) => write!(output, " {first}-{second} "),
+            Event::Moved(_, from, to, _) => {
+                write!(output, "{}→{}", u32::from(*from), u32::from(*to))
+            }
+            Event::MovedAgain(_, _, to, _) => write!(output, "→{}", u32::from(*to)),
+            Event::Return(_, to) => write!(output, "↩{}", u32::from(*to)),
+            Event::Jump(_, to, _) => write!(output, "⇒{}", u32::from(*to)),
+            Event::GooseToGoose(_, _, to, _) => write!(output, "⇢{}", u32::from(*to)),
+            Event::Prank(player, ..) => write!(output, "×{player}"),
+            Event::Win(_) => write!(output, "#"),
+            Event::ExtraTurn(_) => write!(output, "↻"),
+            Event::PlayerJoined(_)
+            | Event::PlayerLeft(_)
+            | Event::PlayerRenamed(..)
+            | Event::Bounced(_)
+            | Event::PositionSet(..)
+            | Event::Kicked(_)
+            | Event::Reset
+            | Event::Rematch => Ok(()),
+        }
+        .expect("writing to a string");
+    }
+}
+
+/// Writes a game as a header of tags followed by one record per line: `+Name` and
+/// `-Name` for players joining and leaving, and moves such as `Pippo 4-2 0→6⇒12`.
+/// Renames are written as `~Name New`, admin commands as `=Name 12`, `!Name`, `reset`
+/// and `rematch`.
+pub fn write(notation: &Notation) -> String {
+    let players = notation
+        .log
+        .iter()
+        .map_while(|(command, _)| match command {
+            Command::Add(player) => Some(player.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let mut output = format!(
+        "[Variant \"{}\"]\n[Seed \"{}\"]\n[Players \"{}\"]\n\n",
+        notation.variant,
+        notation.seed,
+        players.join(" ")
+    );
+
+    for (command, events) in &notation.log[players.len()..] {
+        match command {
+            Command::Add(player) => write!(output, "+{player}").expect("writing to a string"),
+            Command::Remove(player) => write!(output, "-{player}").expect("writing to a string"),
+            Command::Move(player, _) | Command::RollAndMove(player) => {
+                write_move(&mut output, player, events)
+            }
+            Command::SetPosition(player, position) => {
+                write!(output, "={player} {position}").expect("writing to a string")
+            }
+            Command::Kick(player) => write!(output, "!{player}").expect("writing to a string"),
+            Command::Rename(player, name) => {
+                write!(output, "~{player} {name}").expect("writing to a string")
+            }
+            Command::Reset => output.push_str("reset"),
+            Command::Rematch => output.push_str("rematch"),
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+fn tag<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    line.strip_prefix('[')?
+        .strip_suffix(']')?
+        .strip_prefix(name)?
+        .strip_prefix(" \"")?
+        .strip_suffix('"')
+}
+
+fn name(text: &str) -> Option<String> {
+    (!text.is_empty()
+        && !text.starts_with(['+', '-', '=', '!', '~'])
+        && !text.contains(|c: char| c.is_whitespace() || "→↩⇒⇢×#↻[]\"".contains(c)))
+    .then(|| text.to_string())
+}
+
+fn number(text: &str) -> (Option<SamplePosition>, &str) {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+
+    (
+        text[..end].parse::<u32>().ok().map(SamplePosition::from),
+        &text[end..],
+    )
+}
+
+fn parse_path(player: &str, path: &str) -> Option<Vec<GameEvent>> {
+    let (from, mut rest) = number(path);
+    let initial = from?;
+    let mut position = initial;
+    let mut moved = false;
+    let mut sequence = 0;
+    let mut events = Vec::new();
+    let mut landing = |to: SamplePosition, cause| {
+        sequence += 1;
+        Landing {
+            square: to.get_type(),
+            cause,
+            sequence: sequence - 1,
+        }
+    };
+
+    while let Some(symbol) = rest.chars().next() {
+        rest = &rest[symbol.len_utf8()..];
+
+        match symbol {
+            '→' | '↩' | '⇒' | '⇢' => {
+                let (to, tail) = number(rest);
+                let to = to?;
+                rest = tail;
+
+                let next = match symbol {
+                    '→' if moved => vec![Event::MovedAgain(
+                        player.to_string(),
+                        position,
+                        to,
+                        landing(to, Cause::TheGoose),
+                    )],
+                    '→' => vec![Event::Moved(
+                        player.to_string(),
+                        position,
+                        to,
+                        landing(to, Cause::Roll),
+                    )],
+                    '↩' => {
+                        if let Some(Event::Moved(.., last) | Event::MovedAgain(.., last)) =
+                            events.last_mut()
+                        {
+                            last.square = to.get_type();
+                        }
+                        vec![
+                            Event::Bounced(player.to_string()),
+                            Event::Return(player.to_string(), to),
+                        ]
+                    }
+                    '⇒' => vec![Event::Jump(
+                        player.to_string(),
+                        to,
+                        landing(to, Cause::TheBridge),
+                    )],
+                    _ => vec![Event::GooseToGoose(
+                        player.to_string(),
+                        position,
+                        to,
+                        landing(to, Cause::GooseToGoose),
+                    )],
+                };
+                events.extend(next);
+                moved = true;
+                position = to;
+            }
+            '×' => {
+                let end = rest
+                    .find(['→', '↩', '⇒', '⇢', '×', '#', '↻'])
+                    .unwrap_or(rest.len());
+                events.push(Event::Prank(name(&rest[..end])?, position, initial));
+                rest = &rest[end..];
+            }
+            '#' if rest.is_empty() => events.push(Event::Win(player.to_string())),
+            '↻' if rest.is_empty() => events.push(Event::ExtraTurn(player.to_string())),
+            _ => return None,
+        }
+    }
+
+    moved.then_some(events)
+}
+
+#[allow(clippy::type_complexity)]
+fn parse_record(line: &str) -> Option<(Command<String, [u32; 2]>, Vec<GameEvent>)> {
+    if let Some(player) = line.strip_prefix('+') {
+        let player = name(player)?;
+        return Some((
+            Command::Add(player.clone()),
+            vec![Event::PlayerJoined(player)],
+        ));
+    }
+    if let Some(player) = line.strip_prefix('-') {
+        let player = name(player)?;
+        return Some((
+            Command::Remove(player.clone()),
+            vec![Event::PlayerLeft(player)],
+        ));
+    }
+    if let Some(record) = line.strip_prefix('~') {
+        let (player, new) = record.split_once(' ')?;
+        let (player, new) = (name(player)?, name(new)?);
+        return Some((
+            Command::Rename(player.clone(), new.clone()),
+            vec![Event::PlayerRenamed(player, new)],
+        ));
+    }
+
+    if let Some(record) = line.strip_prefix('=') {
+        let (player, position) = record.split_once(' ')?;
+        let player = name(player)?;
+        let position = position.parse().ok()?;
+        return Some((
+            Command::SetPosition(player.clone(), position),
+            vec![Event::PositionSet(player, SamplePosition::from(position))],
+        ));
+    }
+    if let Some(player) = line.strip_prefix('!') {
+        let player = name(player)?;
+        return Some((Command::Kick(player.clone()), vec![Event::Kicked(player)]));
+    }
+    match line {
+        "reset" => return Some((Command::Reset, vec![Event::Reset])),
+        "rematch" => return Some((Command::Rematch, vec![Event::Rematch])),
+        _ => {}
+    }
+
+    let mut words = line.split(' ');
+    let (player, roll, path) = (name(words.next()?)?, words.next()?, words.next()?);
+    if words.next().is_some() {
+        return None;
+    }
+
+    let (first, second) = roll.split_once('-')?;
+    let roll = [first.parse().ok()?, second.parse().ok()?];
+    let mut events = vec![Event::Roll(player.clone(), roll)];
+    events.extend(parse_path(&player, path)?);
+
+    Some((Command::Move(player, roll), events))
+}
+
+fn rules(variant: &str) -> Result<(GooseRule, ExtraTurns), NotationError> {
+    match variant {
+        CLASSIC => Ok((GooseRule::MoveAgain, ExtraTurns::default())),
+        SPANISH => Ok((
+            GooseRule::GooseToGoose,
+            ExtraTurns {
+                dice: true,
+                ..ExtraTurns::default()
+            },
+        )),
+        _ => Err(NotationError::UnknownVariant(variant.to_string())),
+    }
+}
+
+fn parse_lines(text: &str) -> Result<(Notation, Vec<usize>), NotationError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()));
+    let mut header = |name| {
+        let (i, line) = lines.next().unwrap_or((0, ""));
+        tag(line, name)
+            .map(|value| (i, value))
+            .ok_or(NotationError::Syntax(i))
+    };
+
+    let (_, variant) = header("Variant")?;
+    rules(variant)?;
+    let (line, seed) = header("Seed")?;
+    let seed = seed.parse().map_err(|_| NotationError::Syntax(line))?;
+    let (line, names) = header("Players")?;
+
+    let records = names
+        .split_whitespace()
+        .map(|player| (line, format!("+{player}")))
+        .chain(
+            lines
+                .filter(|(_, record)| !record.is_empty())
+                .map(|(line, record)| (line, record.to_string())),
+        );
+    let (mut log, mut numbers) = (Vec::new(), Vec::new());
+    for (line, record) in records {
+        log.push(parse_record(&record).ok_or(NotationError::Syntax(line))?);
+        numbers.push(line);
+    }
+
+    Ok((
+        Notation {
+            variant: variant.to_string(),
+            seed,
+            log,
+        },
+        numbers,
+    ))
+}
+
+/// Parses a game without playing it: the events of every record are rebuilt from its
+/// notation alone.
+pub fn parse(text: &str) -> Result<Notation, NotationError> {
+    parse_lines(text).map(|(notation, _)| notation)
+}
+
+/// Parses a game and replays it through `TheGoose::execute`, checking that every
+/// record produces exactly the events written down.
+pub fn load(
+    text: &str,
+) -> Result<(Notation, SampleTheGoose<String, SamplePosition>), NotationError> {
+    let (notation, lines) = parse_lines(text)?;
+    let (goose_rule, extra_turns) = rules(&notation.variant)?;
+    let mut the_goose = SampleTheGoose::with_die(SampleDie::new(1..=6, notation.seed))
+        .with_goose_rule(goose_rule)
+        .with_extra_turns(extra_turns);
+
+    for ((command, events), line) in notation.log.iter().zip(lines) {
+        match collect(&mut the_goose, command.clone()) {
+            Ok(played) if played.as_slice() == events.as_slice() => {}
+            Ok(_) => return Err(NotationError::Diverged(line)),
+            Err(e) => return Err(NotationError::Rejected(line, render_error(&e))),
+        }
+    }
+
+    Ok((notation, the_goose))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::replay::record;
+    use crate::test::landing;
+    use crate::PositionType;
+
+    fn name(name: &str) -> String {
+        name.to_string()
+    }
+
+    const GAME: &str = "[Variant \"classic\"]
+[Seed \"42\"]
+[Players \"Pippo Pluto\"]
+
+Pluto 2-3 0→5→10
+Pippo 3-3 0→6⇒12
+Pluto 1-1 10→12×Pippo
++Paperino
+Pippo 6-6 10→22
+Pluto 1-2 12→15
+Paperino 4-5 0→9→18→27→36
+-Pluto
+Pippo 6-6 22→34
+Paperino 1-1 36→38
+Pippo 6-6 34→46
+Paperino 1-2 38→41
+Pippo 6-6 46→58
+Paperino 2-1 41→44
+Pippo 3-4 58→63↩61
+Paperino 1-1 44→46
+Pippo 1-1 61→63#
+";
+
+    #[test]
+    fn test_round_trip() {
+        let notation = parse(GAME).unwrap();
+
+        assert_eq!(notation.variant, CLASSIC);
+        assert_eq!(notation.seed, 42);
+        assert_eq!(notation.log.len(), 19);
+        assert_eq!(
+            notation.log[4],
+            (
+                Command::Move(name("Pluto"), [1, 1]),
+                vec![
+                    Event::Roll(name("Pluto"), [1, 1]),
+                    Event::Moved(
+                        name("Pluto"),
+                        10.into(),
+                        12.into(),
+                        landing(PositionType::Normal, Cause::Roll, 0)
+                    ),
+                    Event::Prank(name("Pippo"), 12.into(), 10.into()),
+                ]
+            )
+        );
+        assert_eq!(write(&notation), GAME);
+    }
+
+    #[test]
+    fn test_write_recorded() {
+        let mut the_goose = SampleTheGoose::<String, SamplePosition>::new();
+        let log = record(
+            &mut the_goose,
+            [
+                Command::Add(name("Pippo")),
+                Command::Add(name("Pluto")),
+                Command::Move(name("Pluto"), [2, 3]),
+                Command::Move(name("Pippo"), [3, 3]),
+                Command::Move(name("Pluto"), [1, 1]),
+            ],
+        )
+        .unwrap();
+        let notation = Notation {
+            variant: name(CLASSIC),
+            seed: 0,
+            log,
+        };
+
+        let text = write(&notation);
+        assert!(text.ends_with("\n\nPluto 2-3 0→5→10\nPippo 3-3 0→6⇒12\nPluto 1-1 10→12×Pippo\n"));
+        assert_eq!(parse(&text), Ok(notation));
+    }
+
+    #[test]
+    fn test_load() {
+        let (notation, the_goose) = load(GAME).unwrap();
+
+        assert_eq!(notation, parse(GAME).unwrap());
+        assert_eq!(
+            the_goose.iter().collect::<Vec<_>>(),
+            vec![
+                (&name("Paperino"), &46.into()),
+                (&name("Pippo"), &63.into())
+            ]
+        );
+
+        assert_eq!(
+            load(&GAME.replace("Pippo 6-6 10→22", "Pippo 6-6 10→23")).err(),
+            Some(NotationError::Diverged(9))
+        );
+        assert_eq!(
+            load(&GAME.replace("+Paperino", "+Pippo")).err(),
+            Some(NotationError::Rejected(
+                8,
+                name("Pippo: already existing player")
+            ))
+        );
+        assert_eq!(
+            load(&GAME.replace("Pluto 2-3", "Pluto 2-9")).err(),
+            Some(NotationError::Rejected(5, name("Pluto: invalid roll 9")))
+        );
+    }
+
+    #[test]
+    fn test_load_spanish() {
+        let game = "[Variant \"spanish\"]
+[Seed \"7\"]
+[Players \"Pippo Pluto\"]
+
+Pippo 2-2 0→4
+Pluto 2-1 0→3
+Pippo 3-2 4→9⇢14↻
+Pippo 6-6 14→26↻
+Pippo 1-2 26→29
+";
+        let (notation, the_goose) = load(game).unwrap();
+
+        assert_eq!(
+            notation.log[4].1[2],
+            Event::GooseToGoose(
+                name("Pippo"),
+                9.into(),
+                14.into(),
+                landing(PositionType::TheGoose, Cause::GooseToGoose, 1)
+            )
+        );
+        assert_eq!(write(&notation), game);
+        assert_eq!(the_goose.get("Pippo"), Some(&29.into()));
+        assert_eq!(
+            load(&game.replace("spanish", CLASSIC)).err(),
+            Some(NotationError::Diverged(7))
+        );
+    }
+
+    #[test]
+    fn test_admin_records() {
+        let game = "[Variant \"classic\"]
+[Seed \"1\"]
+[Players \"Pippo Pluto\"]
+
+=Pippo 60
+Pippo 1-2 60→63#
+rematch
+~Pluto Paperino
+!Paperino
+Pippo 2-2 0→4
+reset
+";
+        let (notation, the_goose) = load(game).unwrap();
+
+        assert_eq!(write(&notation), game);
+        assert_eq!(
+            notation.log[2],
+            (
+                Command::SetPosition(name("Pippo"), 60),
+                vec![Event::PositionSet(name("Pippo"), 60.into())]
+            )
+        );
+        assert_eq!(
+            the_goose.iter().collect::<Vec<_>>(),
+            vec![(&name("Pippo"), &0.into())]
+        );
+        assert_eq!(
+            notation.log[5],
+            (
+                Command::Rename(name("Pluto"), name("Paperino")),
+                vec![Event::PlayerRenamed(name("Pluto"), name("Paperino"))]
+            )
+        );
+        assert_eq!(
+            load(&game.replace("!Paperino", "!Pluto")).err(),
+            Some(NotationError::Rejected(9, name("Pluto: unknown player")))
+        );
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        assert_eq!(
+            parse(&GAME.replace("classic", "oca")),
+            Err(NotationError::UnknownVariant(name("oca")))
+        );
+        assert_eq!(
+            parse(&GAME.replace("\"42\"", "\"x\"")),
+            Err(NotationError::Syntax(2))
+        );
+        assert_eq!(
+            parse(&GAME.replace("0→6⇒12", "0→6⇒")),
+            Err(NotationError::Syntax(6))
+        );
+        assert_eq!(
+            parse(&GAME.replace("Pippo 3-3", "Pippo 3")),
+            Err(NotationError::Syntax(6))
+        );
+        assert_eq!(
+            parse(&GAME.replace("61→63#", "61→63#→1")),
+            Err(NotationError::Syntax(21))
+        );
+        assert_eq!(parse(""), Err(NotationError::Syntax(0)));
+    }
+}
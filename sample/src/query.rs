@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+
+use the_goose::overlay::DryRun;
+
+use crate::{Error, Position, Roll, SampleEvents, State, TheGoose};
+
+pub trait Query<Player, P, II: IntoIterator<Item = Player>>: State<Player, P, II> {
+    fn leaderboard(&self) -> Result<Vec<(Player, P)>, Self::Error>
+    where
+        P: Into<u32> + Copy,
+    {
+        let mut leaderboard = Vec::new();
+        for player in self.players()? {
+            if let Some(position) = self.get_player_position(&player)? {
+                leaderboard.push((player, position));
+            }
+        }
+        leaderboard.sort_by_key(|(_, position)| std::cmp::Reverse((*position).into()));
+
+        Ok(leaderboard)
+    }
+
+    fn leaders(&self) -> Result<Vec<Player>, Self::Error>
+    where
+        P: Into<u32> + Copy,
+    {
+        let leaderboard = self.leaderboard()?;
+        let first = leaderboard.first().map(|(_, position)| (*position).into());
+
+        Ok(leaderboard
+            .into_iter()
+            .take_while(|(_, position)| Some((*position).into()) == first)
+            .map(|(player, _)| player)
+            .collect())
+    }
+
+    fn distance_to_end(&self, player: &Player) -> Result<Option<u32>, Self::Error>
+    where
+        P: Position<u32> + Into<u32>,
+    {
+        Ok(self
+            .get_player_position(player)?
+            .map(|position| P::end().into().saturating_sub(position.into())))
+    }
+
+    fn occupancy(&self) -> Result<BTreeMap<P, Vec<Player>>, Self::Error>
+    where
+        P: Ord,
+    {
+        let mut occupancy = BTreeMap::<P, Vec<Player>>::new();
+        for player in self.players()? {
+            if let Some(position) = self.get_player_position(&player)? {
+                occupancy.entry(position).or_default().push(player);
+            }
+        }
+
+        Ok(occupancy)
+    }
+}
+
+impl<Player, P, II, S> Query<Player, P, II> for S
+where
+    II: IntoIterator<Item = Player>,
+    S: State<Player, P, II>,
+{
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Preview<Player, P, R> {
+    pub events: SampleEvents<Player, P, R>,
+    pub positions: BTreeMap<Player, P>,
+}
+
+#[allow(clippy::type_complexity)]
+pub fn preview<Player, P, R, II, G>(
+    the_goose: &G,
+    player: &Player,
+    roll: R,
+) -> Result<
+    Preview<Player, P, R>,
+    Error<Player, R::Face, <G::State as State<Player, P, II>>::Error, Infallible>,
+>
+where
+    Player: Ord + Clone,
+    P: Position<R::Step> + Copy + PartialEq,
+    R: Roll + Clone,
+    R::Face: Clone + PartialEq,
+    R::Step: Copy,
+    II: IntoIterator<Item = Player> + FromIterator<Player>,
+    G: TheGoose<Player, P, R, II>,
+{
+    let mut dry_run = DryRun::new(the_goose, roll.clone());
+    let mut events = SampleEvents::default();
+
+    dry_run.move_player(player, roll, &mut events)?;
+
+    Ok(Preview {
+        events,
+        positions: dry_run
+            .into_changes()
+            .into_iter()
+            .filter_map(|(player, position)| Some((player, position?)))
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::indexed::IndexedTheGoose;
+    use crate::test::landing;
+    use crate::{collect, Cause, Command, Event, PositionType, SamplePosition, SampleTheGoose};
+
+    fn the_goose() -> SampleTheGoose<&'static str, SamplePosition> {
+        let mut the_goose = SampleTheGoose::new();
+
+        for (player, position) in [
+            ("Pippo", 10),
+            ("Pluto", 17),
+            ("Paperino", 17),
+            ("Topolino", 3),
+        ] {
+            the_goose.insert(player, SamplePosition(position));
+        }
+
+        the_goose
+    }
+
+    #[test]
+    fn test_leaderboard() {
+        let the_goose = the_goose();
+
+        assert_eq!(
+            the_goose.leaderboard(),
+            Ok(vec![
+                ("Paperino", SamplePosition(17)),
+                ("Pluto", SamplePosition(17)),
+                ("Pippo", SamplePosition(10)),
+                ("Topolino", SamplePosition(3)),
+            ])
+        );
+        assert_eq!(the_goose.leaders(), Ok(vec!["Paperino", "Pluto"]));
+        assert_eq!(
+            SampleTheGoose::<&str, SamplePosition>::new().leaders(),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    fn test_distance_to_end() {
+        let the_goose = the_goose();
+
+        assert_eq!(the_goose.distance_to_end(&"Pippo"), Ok(Some(53)));
+        assert_eq!(the_goose.distance_to_end(&"Minnie"), Ok(None));
+    }
+
+    #[test]
+    fn test_occupancy() {
+        assert_eq!(
+            the_goose().occupancy(),
+            Ok(BTreeMap::from([
+                (SamplePosition(3), vec!["Topolino"]),
+                (SamplePosition(10), vec!["Pippo"]),
+                (SamplePosition(17), vec!["Paperino", "Pluto"]),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_preview() {
+        let the_goose = the_goose();
+
+        assert_eq!(
+            preview(&the_goose, &"Topolino", [3, 4]),
+            Ok(Preview {
+                events: vec![
+                    Event::Roll("Topolino", [3, 4]),
+                    Event::Moved(
+                        "Topolino",
+                        SamplePosition(3),
+                        SamplePosition(10),
+                        landing(PositionType::Normal, Cause::Roll, 0)
+                    ),
+                    Event::Prank("Pippo", SamplePosition(10), SamplePosition(3)),
+                ]
+                .into(),
+                positions: BTreeMap::from([
+                    ("Pippo", SamplePosition(3)),
+                    ("Topolino", SamplePosition(10)),
+                ]),
+            })
+        );
+
+        assert_eq!(
+            preview(&the_goose, &"Topolino", [1, 2]).map(|preview| preview.positions),
+            Ok(BTreeMap::from([("Topolino", SamplePosition(12))]))
+        );
+
+        assert_eq!(
+            preview(&the_goose, &"Pippo", [4, 3]).map(|preview| preview.positions),
+            Ok(BTreeMap::from([
+                ("Paperino", SamplePosition(10)),
+                ("Pippo", SamplePosition(17)),
+                ("Pluto", SamplePosition(10)),
+            ]))
+        );
+
+        assert_eq!(
+            preview(&the_goose, &"Minnie", [1, 2]),
+            Err(Error::PlayerNotFound("Minnie"))
+        );
+        assert_eq!(
+            preview(&the_goose, &"Pippo", [1, 7]),
+            Err(Error::InvalidRoll("Pippo", 7))
+        );
+
+        assert_eq!(the_goose.get("Pippo"), Some(&SamplePosition(10)));
+        assert_eq!(the_goose.get("Topolino"), Some(&SamplePosition(3)));
+    }
+
+    #[test]
+    fn test_preview_indexed() {
+        let mut the_goose = IndexedTheGoose::new();
+
+        collect(&mut the_goose, Command::Add("Pippo")).ok();
+        collect(&mut the_goose, Command::Add("Pluto")).ok();
+        the_goose
+            .update_player_position(&"Pluto", &SamplePosition(5))
+            .ok();
+
+        assert_eq!(
+            preview(&the_goose, &"Pippo", [2, 3]).map(|preview| preview.events),
+            Ok(vec![
+                Event::Roll("Pippo", [2, 3]),
+                Event::Moved(
+                    "Pippo",
+                    SamplePosition(0),
+                    SamplePosition(5),
+                    landing(PositionType::TheGoose, Cause::Roll, 0)
+                ),
+                Event::Prank("Pluto", SamplePosition(5), SamplePosition(0)),
+                Event::MovedAgain(
+                    "Pippo",
+                    SamplePosition(5),
+                    SamplePosition(10),
+                    landing(PositionType::Normal, Cause::TheGoose, 1)
+                ),
+            ]
+            .into())
+        );
+        assert_eq!(
+            the_goose
+                .find_players_by_position(&SamplePosition(5))
+                .map(|players| players.into_iter().collect::<Vec<_>>()),
+            Ok(vec!["Pluto"])
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use std::fmt::{Display, Write};
+
+use crate::svg::{colour, escape, pawn_centre, render_board, size, spiral};
+use crate::text::{format, render};
+use crate::{collect, Command, Error, Event, Position, Roll, SamplePosition, State, TheGoose};
+
+const HOP: u32 = 250;
+const JUMP: u32 = 600;
+const PAUSE: u32 = 800;
+const CAPTIONS: u32 = 50;
+
+pub type Log<Player, R> = Vec<(Command<Player, R>, Vec<Event<Player, SamplePosition, R>>)>;
+
+#[allow(clippy::type_complexity)]
+pub fn record<Player, R, G>(
+    the_goose: &mut G,
+    commands: impl IntoIterator<Item = Command<Player, R>>,
+) -> Result<
+    Log<Player, R>,
+    Error<
+        Player,
+        R::Face,
+        <G::State as State<Player, SamplePosition, Vec<Player>>>::Error,
+        std::convert::Infallible,
+    >,
+>
+where
+    Player: Clone + PartialEq,
+    R: Roll + Clone,
+    R::Face: Clone + PartialEq,
+    R::Step: Copy,
+    SamplePosition: Position<R::Step>,
+    G: TheGoose<Player, SamplePosition, R, Vec<Player>>,
+{
+    let mut log = Vec::new();
+
+    for command in commands {
+        let events = collect(the_goose, command.clone())?;
+        log.push((command, events.to_vec()));
+    }
+
+    Ok(log)
+}
+
+struct Pawn<Player> {
+    player: Player,
+    position: SamplePosition,
+    visible: bool,
+    animations: String,
+}
+
+impl<Player> Pawn<Player> {
+    fn show(&mut self, visible: bool, time: u32) {
+        self.visible = visible;
+        writeln!(
+            self.animations,
+            r#"    <set attributeName="visibility" to="{}" begin="{time}ms" fill="freeze"/>"#,
+            if visible { "visible" } else { "hidden" }
+        )
+        .expect("writing to a string");
+    }
+
+    fn travel(
+        &mut self,
+        cells: &[(u32, u32)],
+        slot: usize,
+        path: &[SamplePosition],
+        time: u32,
+        duration: u32,
+    ) {
+        let values = path
+            .iter()
+            .map(|position| {
+                let (x, y) = pawn_centre(cells, position, slot);
+                format!("{x} {y}")
+            })
+            .collect::<Vec<_>>()
+            .join(";");
+
+        self.position = *path.last().unwrap_or(&self.position);
+        writeln!(
+            self.animations,
+            r#"    <animateTransform attributeName="transform" type="translate" values="{values}" begin="{time}ms" dur="{duration}ms" fill="freeze"/>"#
+        )
+        .expect("writing to a string");
+    }
+}
+
+fn pawn<'a, Player: PartialEq + Clone>(
+    pawns: &'a mut Vec<Pawn<Player>>,
+    player: &Player,
+) -> (usize, &'a mut Pawn<Player>) {
+    let slot = match pawns.iter().position(|pawn| pawn.player == *player) {
+        Some(slot) => slot,
+        None => {
+            pawns.push(Pawn {
+                player: player.clone(),
+                position: SamplePosition::from(0),
+                visible: false,
+                animations: String::new(),
+            });
+            pawns.len() - 1
+        }
+    };
+
+    (slot, &mut pawns[slot])
+}
+
+fn steps(from: SamplePosition, to: SamplePosition) -> Vec<SamplePosition> {
+    let (from, to) = (u32::from(from), u32::from(to));
+
+    if from <= to {
+        (from..=to).map(SamplePosition::from).collect()
+    } else {
+        (to..=from).rev().map(SamplePosition::from).collect()
+    }
+}
+
+fn caption(output: &mut String, text: &str, y: u32, time: u32, duration: Option<u32>) {
+    writeln!(
+        output,
+        r#"  <text x="10" y="{y}" font-size="14" visibility="hidden">{}<set attributeName="visibility" to="visible" begin="{time}ms" {}/></text>"#,
+        escape(text),
+        match duration {
+            Some(duration) => format!(r#"dur="{duration}ms""#),
+            None => r#"fill="freeze""#.to_string(),
+        }
+    )
+    .expect("writing to a string");
+}
+
+/// Renders a recorded game as an SVG whose SMIL animations replay every command and
+/// event step by step, with the command and the current event as captions.
+#[allow(clippy::type_complexity)]
+pub fn animate<Player, F, const N: usize>(
+    log: &[(
+        Command<Player, [F; N]>,
+        Vec<Event<Player, SamplePosition, [F; N]>>,
+    )],
+) -> String
+where
+    Player: Display + PartialEq + Clone,
+    F: Display,
+{
+    let cells = spiral(u32::from(SamplePosition::end()) + 1);
+    let size = size(&cells);
+    let mut pawns: Vec<Pawn<Player>> = Vec::new();
+    let mut captions = String::new();
+    let mut time = 0;
+
+    for (i, (command, events)) in log.iter().enumerate() {
+        let start = time;
+
+        for (j, event) in events.iter().enumerate() {
+            let duration = match event {
+                Event::PlayerJoined(player) => {
+                    let (slot, pawn) = pawn(&mut pawns, player);
+                    if !pawn.visible {
+                        pawn.travel(&cells, slot, &[SamplePosition::from(0)], time, PAUSE);
+                        pawn.show(true, time);
+                    }
+                    PAUSE
+                }
+                Event::PlayerLeft(player) | Event::Kicked(player) => {
+                    pawn(&mut pawns, player).1.show(false, time);
+                    PAUSE
+                }
+                Event::PlayerRenamed(player, name) => {
+                    pawn(&mut pawns, player).1.player = name.clone();
+                    PAUSE
+                }
+                Event::Moved(player, from, to, _) | Event::MovedAgain(player, from, to, _) => {
+                    let (slot, pawn) = pawn(&mut pawns, player);
+                    let path = steps(*from, *to);
+                    let duration = HOP * (path.len() as u32 - 1).max(1);
+                    pawn.travel(&cells, slot, &path, time, duration);
+                    duration
+                }
+                Event::Return(player, to) => {
+                    let (slot, pawn) = pawn(&mut pawns, player);
+                    let path = steps(pawn.position, *to);
+                    let duration = HOP * (path.len() as u32 - 1).max(1);
+                    pawn.travel(&cells, slot, &path, time, duration);
+                    duration
+                }
+                Event::Jump(player, to, _) | Event::PositionSet(player, to) => {
+                    let (slot, pawn) = pawn(&mut pawns, player);
+                    pawn.travel(&cells, slot, &[pawn.position, *to], time, JUMP);
+                    JUMP
+                }
+                Event::Prank(player, from, to) | Event::GooseToGoose(player, from, to, _) => {
+                    let (slot, pawn) = pawn(&mut pawns, player);
+                    pawn.travel(&cells, slot, &[*from, *to], time, JUMP);
+                    JUMP
+                }
+                Event::Reset | Event::Rematch => {
+                    let start = SamplePosition::from(0);
+                    for (slot, pawn) in pawns.iter_mut().enumerate() {
+                        if pawn.visible {
+                            pawn.travel(&cells, slot, &[pawn.position, start], time, JUMP);
+                        }
+                    }
+                    JUMP
+                }
+                Event::Roll(..) | Event::Bounced(_) | Event::Win(_) | Event::ExtraTurn(_) => PAUSE,
+            };
+
+            let last = i + 1 == log.len() && j + 1 == events.len();
+            caption(
+                &mut captions,
+                &render(core::slice::from_ref(event)),
+                size + 40,
+                time,
+                (!last).then_some(duration),
+            );
+            time += duration;
+        }
+
+        caption(
+            &mut captions,
+            &format(command),
+            size + 18,
+            start,
+            (i + 1 != log.len()).then_some(time - start),
+        );
+    }
+
+    let mut output = String::new();
+    writeln!(
+        output,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{}" viewBox="0 0 {size} {}" font-family="sans-serif">"#,
+        size + CAPTIONS,
+        size + CAPTIONS
+    )
+    .expect("writing to a string");
+
+    render_board(&mut output, &cells);
+    output.push_str(&captions);
+
+    for (slot, pawn) in pawns.iter().enumerate() {
+        let (x, y) = pawn_centre(&cells, &SamplePosition::from(0), slot);
+        let player = pawn.player.to_string();
+
+        writeln!(
+            output,
+            r##"  <g class="pawn" visibility="hidden" transform="translate({x} {y})"><title>{}</title><circle r="7" fill="{}" stroke="#000000"/><text y="3" font-size="9" text-anchor="middle" fill="#ffffff">{}</text>"##,
+            escape(&player),
+            colour(slot),
+            escape(&player.chars().next().map(String::from).unwrap_or_default()),
+        )
+        .expect("writing to a string");
+        output.push_str(&pawn.animations);
+        output.push_str("  </g>\n");
+    }
+
+    output.push_str("</svg>\n");
+
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::svg::test::snapshot;
+    use crate::SampleTheGoose;
+
+    #[test]
+    fn test_animate() {
+        let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
+        let log = record(
+            &mut the_goose,
+            [
+                Command::Add("Pippo"),
+                Command::Add("Pluto"),
+                Command::Move("Pluto", [2, 3]),
+                Command::Move("Pippo", [3, 3]),
+                Command::Move("Pluto", [1, 1]),
+                Command::Move("Pippo", [6, 6]),
+                Command::Move("Pluto", [1, 2]),
+                Command::Move("Pippo", [6, 6]),
+                Command::Move("Pluto", [1, 1]),
+                Command::Move("Pippo", [6, 6]),
+                Command::Remove("Pluto"),
+                Command::Move("Pippo", [6, 6]),
+                Command::Move("Pippo", [3, 4]),
+                Command::Move("Pippo", [1, 1]),
+            ],
+        )
+        .unwrap();
+
+        let svg = animate(&log);
+
+        assert_eq!(svg, animate(&log));
+        for caption in [
+            "Pippo moves from Start to The Bridge",
+            "Pippo jumps to 12",
+            "Pluto moves again and goes to 10",
+            "On 12 there is Pippo, who returns to 10",
+            "Pippo bounces!",
+            "Pippo Wins!!",
+            "remove player Pluto",
+        ] {
+            assert!(svg.contains(caption), "{caption}");
+        }
+        assert_eq!(svg.matches("<animateTransform").count(), 17);
+        snapshot("replay", &svg);
+    }
+
+    #[test]
+    fn test_record() {
+        let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
+
+        assert_eq!(
+            record(
+                &mut the_goose,
+                [Command::Add("Pippo"), Command::Add("Pippo")]
+            ),
+            Err(Error::DuplicatePlayer("Pippo"))
+        );
+    }
+}
@@ -0,0 +1,330 @@
+use std::fmt::{Display, Write};
+
+use crate::{Event, Position, PositionType, SamplePosition, State};
+
+const CELL: u32 = 60;
+const SQUARE: u32 = 56;
+const MARGIN: u32 = 10;
+
+const COLOURS: [&str; 8] = [
+    "#d62728", "#1f77b4", "#9467bd", "#2ca02c", "#ff7f0e", "#17becf", "#e377c2", "#7f7f7f",
+];
+
+pub fn side(squares: u32) -> u32 {
+    (0..)
+        .find(|side| side * side >= squares)
+        .unwrap_or_default()
+}
+
+pub fn spiral(squares: u32) -> Vec<(u32, u32)> {
+    let side = side(squares) as i64;
+    let mut cells = Vec::with_capacity(squares as usize);
+    let (mut top, mut left, mut bottom, mut right) = (0, 0, side - 1, side - 1);
+
+    while top <= bottom && left <= right {
+        cells.extend((left..=right).map(|x| (x, top)));
+        top += 1;
+        cells.extend((top..=bottom).map(|y| (right, y)));
+        right -= 1;
+        if top <= bottom {
+            cells.extend((left..=right).rev().map(|x| (x, bottom)));
+            bottom -= 1;
+        }
+        if left <= right {
+            cells.extend((top..=bottom).rev().map(|y| (left, y)));
+            left += 1;
+        }
+    }
+
+    cells
+        .into_iter()
+        .take(squares as usize)
+        .map(|(x, y)| (x as u32, y as u32))
+        .collect()
+}
+
+pub fn origin(cells: &[(u32, u32)], position: &SamplePosition) -> (u32, u32) {
+    let (x, y) = cells[position.0 as usize];
+
+    (MARGIN + x * CELL, MARGIN + y * CELL)
+}
+
+pub fn centre(cells: &[(u32, u32)], position: &SamplePosition) -> (u32, u32) {
+    let (x, y) = origin(cells, position);
+
+    (x + SQUARE / 2, y + SQUARE / 2)
+}
+
+pub fn size(cells: &[(u32, u32)]) -> u32 {
+    side(cells.len() as u32) * CELL + 2 * MARGIN - (CELL - SQUARE)
+}
+
+pub fn pawn_centre(cells: &[(u32, u32)], position: &SamplePosition, slot: usize) -> (u32, u32) {
+    let (x, y) = origin(cells, position);
+    let slot = slot.min(5) as u32;
+
+    (x + 12 + (slot % 3) * 16, y + 36 + (slot / 3) * 14)
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn colour(index: usize) -> &'static str {
+    COLOURS[index % COLOURS.len()]
+}
+
+fn square(position: &SamplePosition) -> (&'static str, &'static str, Option<&'static str>) {
+    match (position.0, position.get_type()) {
+        (0, _) => ("start", "#ffffff", Some("Start")),
+        (_, PositionType::TheGoose) => ("goose", "#f7d154", Some("Goose")),
+        (_, PositionType::TheBridge) => ("bridge", "#8ecae6", Some("Bridge")),
+        (_, PositionType::End) => ("end", "#90be6d", Some("End")),
+        (_, PositionType::Normal) => ("normal", "#ffffff", None),
+    }
+}
+
+pub fn path<Player, R>(events: &[Event<Player, SamplePosition, R>]) -> Vec<SamplePosition> {
+    let mut path: Vec<SamplePosition> = Vec::new();
+
+    for event in events {
+        let (from, to) = match event {
+            Event::Moved(_, from, to, _) | Event::MovedAgain(_, from, to, _) => (*from, *to),
+            Event::Return(_, to) => match path.last() {
+                Some(from) => (*from, *to),
+                None => (*to, *to),
+            },
+            Event::Jump(_, to, _) | Event::GooseToGoose(_, _, to, _) => {
+                path.push(*to);
+                continue;
+            }
+            _ => continue,
+        };
+
+        if path.last() != Some(&from) {
+            path.push(from);
+        }
+        if from.0 <= to.0 {
+            path.extend((from.0 + 1..=to.0).map(SamplePosition));
+        } else {
+            path.extend((to.0..from.0).rev().map(SamplePosition));
+        }
+    }
+
+    path
+}
+
+pub fn render_board(output: &mut String, cells: &[(u32, u32)]) {
+    for i in 0..cells.len() as u32 {
+        let position = SamplePosition(i);
+        let (x, y) = origin(cells, &position);
+        let (class, fill, label) = square(&position);
+
+        writeln!(
+            output,
+            r##"  <g class="square {class}" id="square-{i}"><rect x="{x}" y="{y}" width="{SQUARE}" height="{SQUARE}" rx="6" fill="{fill}" stroke="#333333"/><text x="{}" y="{}" font-size="14">{i}</text>{}</g>"##,
+            x + 5,
+            y + 17,
+            label.map_or(String::new(), |label| format!(
+                r#"<text x="{}" y="{}" font-size="9">{label}</text>"#,
+                x + 5,
+                y + 28
+            ))
+        )
+        .expect("writing to a string");
+    }
+}
+
+pub fn render_path(output: &mut String, cells: &[(u32, u32)], path: &[SamplePosition]) {
+    if path.len() < 2 {
+        return;
+    }
+
+    let points = path
+        .iter()
+        .map(|position| {
+            let (x, y) = centre(cells, position);
+            format!("{x},{y}")
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    writeln!(
+        output,
+        r##"  <polyline class="path" points="{points}" fill="none" stroke="#444444" stroke-width="3" stroke-dasharray="6 4" stroke-linejoin="round"/>"##
+    )
+    .expect("writing to a string");
+}
+
+pub fn render_pawn(
+    output: &mut String,
+    cells: &[(u32, u32)],
+    player: &str,
+    index: usize,
+    position: &SamplePosition,
+    slot: usize,
+) {
+    let (cx, cy) = pawn_centre(cells, position, slot);
+
+    writeln!(
+        output,
+        r##"  <g class="pawn"><title>{}</title><circle cx="{cx}" cy="{cy}" r="7" fill="{}" stroke="#000000"/><text x="{cx}" y="{}" font-size="9" text-anchor="middle" fill="#ffffff">{}</text></g>"##,
+        escape(player),
+        colour(index),
+        cy + 3,
+        escape(&player.chars().next().map(String::from).unwrap_or_default()),
+    )
+    .expect("writing to a string");
+}
+
+/// Renders the board, the players on it and, when given, the path of the last move
+/// described by its `Moved`, `MovedAgain`, `Return`, `Jump` and `GooseToGoose` events.
+#[allow(clippy::type_complexity)]
+pub fn render<Player, S, R, II>(
+    state: &S,
+    last_move: Option<&[Event<Player, SamplePosition, R>]>,
+) -> Result<String, S::Error>
+where
+    Player: Display,
+    S: State<Player, SamplePosition, II>,
+    II: IntoIterator<Item = Player>,
+{
+    let cells = spiral(SamplePosition::end().0 + 1);
+    let size = size(&cells);
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="0 0 {size} {size}" font-family="sans-serif">"#
+    )
+    .expect("writing to a string");
+
+    render_board(&mut output, &cells);
+    if let Some(events) = last_move {
+        render_path(&mut output, &cells, &path(events));
+    }
+
+    let mut occupied = Vec::new();
+    for (index, player) in state.players()?.into_iter().enumerate() {
+        if let Some(position) = state.get_player_position(&player)? {
+            let slot = occupied.iter().filter(|p| **p == position).count();
+            occupied.push(position);
+            render_pawn(
+                &mut output,
+                &cells,
+                &player.to_string(),
+                index,
+                &position,
+                slot,
+            );
+        }
+    }
+
+    output.push_str("</svg>\n");
+
+    Ok(output)
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::path::Path;
+
+    use super::*;
+    use crate::{collect, Command, SampleTheGoose};
+
+    pub(crate) fn snapshot(name: &str, actual: &str) {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("snapshots")
+            .join(format!("{name}.svg"));
+
+        if std::env::var_os("THE_GOOSE_BLESS").is_some() {
+            std::fs::write(&path, actual).unwrap();
+        }
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap_or_default(),
+            actual,
+            "{} is out of date, rerun with THE_GOOSE_BLESS=1",
+            path.display()
+        );
+    }
+
+    #[test]
+    fn test_spiral() {
+        assert_eq!(side(64), 8);
+        assert_eq!(side(10), 4);
+        assert_eq!(spiral(1), vec![(0, 0)]);
+        assert_eq!(
+            spiral(9),
+            vec![
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (2, 1),
+                (2, 2),
+                (1, 2),
+                (0, 2),
+                (0, 1),
+                (1, 1)
+            ]
+        );
+
+        let cells = spiral(64);
+        assert_eq!(cells.len(), 64);
+        assert_eq!(cells[63], (3, 4));
+        assert!((1..64).all(|i| !cells[..i].contains(&cells[i])));
+    }
+
+    #[test]
+    fn test_path() {
+        let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
+        collect(&mut the_goose, Command::Add("Pippo")).unwrap();
+
+        let events = collect(&mut the_goose, Command::Move("Pippo", [2, 3])).unwrap();
+        assert_eq!(
+            path(&events),
+            (0..=10).map(SamplePosition).collect::<Vec<_>>()
+        );
+
+        the_goose.insert("Pippo", SamplePosition(4));
+        let events = collect(&mut the_goose, Command::Move("Pippo", [1, 1])).unwrap();
+        assert_eq!(
+            path(&events),
+            vec![
+                SamplePosition(4),
+                SamplePosition(5),
+                SamplePosition(6),
+                SamplePosition(12)
+            ]
+        );
+
+        the_goose.insert("Pippo", SamplePosition(60));
+        let events = collect(&mut the_goose, Command::Move("Pippo", [3, 2])).unwrap();
+        assert_eq!(
+            path(&events),
+            [60, 61, 62, 63, 62, 61].map(SamplePosition).to_vec()
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
+        collect(&mut the_goose, Command::Add("Pippo")).unwrap();
+        collect(&mut the_goose, Command::Add("Pluto")).unwrap();
+        collect(&mut the_goose, Command::Add("<Paperino>")).unwrap();
+
+        snapshot(
+            "board",
+            &render::<_, _, [u32; 2], Vec<_>>(&the_goose, None).unwrap(),
+        );
+
+        let events = collect(&mut the_goose, Command::Move("Pippo", [2, 3])).unwrap();
+        let svg = render(&the_goose, Some(&events)).unwrap();
+        assert_eq!(svg, render(&the_goose, Some(&events)).unwrap());
+        assert!(svg.contains("&lt;Paperino&gt;"));
+        snapshot("move", &svg);
+    }
+}
@@ -0,0 +1,228 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::locale::Locale;
+use crate::{Command, Error, Event, SamplePosition};
+
+pub fn parse<F: FromStr, const N: usize>(line: &str) -> Option<Command<&str, [F; N]>> {
+    let mut words = line.split_whitespace();
+
+    match (words.next()?, words.next()) {
+        ("add", Some("player")) => Some(Command::Add(single(words)?)),
+        ("remove", Some("player")) => Some(Command::Remove(single(words)?)),
+        ("kick", Some("player")) => Some(Command::Kick(single(words)?)),
+        ("rename", Some("player")) => match (words.next(), words.next(), words.next()) {
+            (Some(player), Some(name), None) => Some(Command::Rename(player, name)),
+            _ => None,
+        },
+        ("set", Some(player)) => Some(Command::SetPosition(player, single(words)?.parse().ok()?)),
+        ("reset", None) => Some(Command::Reset),
+        ("rematch", None) => Some(Command::Rematch),
+        ("move", Some(player)) => {
+            let dice = words.collect::<Vec<_>>().join(" ");
+            if dice.is_empty() {
+                Some(Command::RollAndMove(player))
+            } else {
+                dice.split(',')
+                    .map(|face| face.trim().parse().ok())
+                    .collect::<Option<Vec<F>>>()?
+                    .try_into()
+                    .ok()
+                    .map(|roll| Command::Move(player, roll))
+            }
+        }
+        _ => None,
+    }
+}
+
+pub fn format<Player: Display, F: Display, const N: usize>(
+    command: &Command<Player, [F; N]>,
+) -> String {
+    match command {
+        Command::Add(player) => format!("add player {player}"),
+        Command::Remove(player) => format!("remove player {player}"),
+        Command::Move(player, roll) => format!("move {player} {}", join(roll)),
+        Command::RollAndMove(player) => format!("move {player}"),
+        Command::SetPosition(player, position) => format!("set {player} {position}"),
+        Command::Kick(player) => format!("kick player {player}"),
+        Command::Rename(player, name) => format!("rename player {player} {name}"),
+        Command::Reset => "reset".to_string(),
+        Command::Rematch => "rematch".to_string(),
+    }
+}
+
+fn single<'a>(mut words: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    match (words.next(), words.next()) {
+        (Some(word), None) => Some(word),
+        _ => None,
+    }
+}
+
+fn join<T: Display>(items: impl IntoIterator<Item = T>) -> String {
+    items
+        .into_iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn render<Player: Display, F: Display, const N: usize>(
+    events: &[Event<Player, SamplePosition, [F; N]>],
+) -> String {
+    Locale::En.render(events)
+}
+
+pub fn render_error<Player: Display, F: Display, E: Display, V: Display>(
+    error: &Error<Player, F, E, V>,
+) -> String {
+    Locale::En.render_error(error)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{collect, SampleTheGoose, SimpleTheGoose, State, Turn};
+
+    #[test]
+    fn test_parse() {
+        assert!(matches!(
+            parse::<u32, 2>("add player Pippo"),
+            Some(Command::Add("Pippo"))
+        ));
+        assert!(matches!(
+            parse::<u32, 2>("remove player Pippo"),
+            Some(Command::Remove("Pippo"))
+        ));
+        assert!(matches!(
+            parse::<u32, 2>("move Pippo 4, 2"),
+            Some(Command::Move("Pippo", [4, 2]))
+        ));
+        assert!(matches!(
+            parse::<u32, 3>("move Pippo 4, 2,1"),
+            Some(Command::Move("Pippo", [4, 2, 1]))
+        ));
+        assert!(matches!(
+            parse::<u32, 2>("move Pippo"),
+            Some(Command::RollAndMove("Pippo"))
+        ));
+        assert!(matches!(
+            parse::<u32, 2>("set Pippo 12"),
+            Some(Command::SetPosition("Pippo", 12))
+        ));
+        assert!(matches!(parse::<u32, 2>("reset"), Some(Command::Reset)));
+        assert!(parse::<u32, 2>("set Pippo twelve").is_none());
+        assert!(parse::<u32, 2>("rematch now").is_none());
+        assert!(parse::<u32, 2>("move Pippo 4").is_none());
+        assert!(parse::<u32, 2>("move Pippo 4, x").is_none());
+        assert!(parse::<u32, 2>("add player").is_none());
+        assert!(parse::<u32, 2>("jump Pippo").is_none());
+        assert!(parse::<u32, 2>("rename player Pippo").is_none());
+    }
+
+    #[test]
+    fn test_format() {
+        for line in [
+            "add player Pippo",
+            "remove player Pippo",
+            "move Pippo 4, 2",
+            "move Pippo",
+            "set Pippo 12",
+            "kick player Pippo",
+            "rename player Pippo Pluto",
+            "reset",
+            "rematch",
+        ] {
+            assert_eq!(format(&parse::<u32, 2>(line).unwrap()), line);
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let mut the_goose = SampleTheGoose::<_, SamplePosition>::new();
+
+        assert_eq!(
+            render(&collect(&mut the_goose, Command::Add("Pippo")).unwrap()),
+            "Pippo joins the game"
+        );
+        assert_eq!(
+            render(&collect(&mut the_goose, Command::Add("Pluto")).unwrap()),
+            "Pluto joins the game"
+        );
+        assert_eq!(
+            render_error(&collect(&mut the_goose, Command::Add("Pippo")).unwrap_err()),
+            "Pippo: already existing player"
+        );
+        assert_eq!(
+            render_error(&collect(&mut the_goose, Command::Move("Pluto", [9, 2])).unwrap_err()),
+            "Pluto: invalid roll 9"
+        );
+        assert_eq!(
+            render(&collect(&mut the_goose, Command::Move("Pluto", [2, 2])).unwrap()),
+            "Pluto rolls 2, 2. Pluto moves from Start to 4"
+        );
+
+        the_goose.insert("Pippo", SamplePosition(60));
+        assert_eq!(
+            render(&collect(&mut the_goose, Command::Move("Pippo", [1, 2])).unwrap()),
+            "Pippo rolls 1, 2. Pippo moves from 60 to 63. Pippo Wins!!"
+        );
+
+        the_goose.insert("Pippo", SamplePosition(60));
+        the_goose.update_turn(Turn::default()).unwrap();
+        assert_eq!(
+            render(&collect(&mut the_goose, Command::Move("Pippo", [3, 2])).unwrap()),
+            "Pippo rolls 3, 2. Pippo moves from 60 to 63. Pippo bounces! Pippo returns to 61"
+        );
+
+        assert_eq!(
+            render(&collect(&mut the_goose, Command::SetPosition("Pippo", 5)).unwrap()),
+            "Pippo is sent to 5"
+        );
+        assert_eq!(
+            render(&collect(&mut the_goose, Command::Reset).unwrap()),
+            "Everyone goes back to Start"
+        );
+        assert_eq!(
+            render(&collect(&mut the_goose, Command::Kick("Pluto")).unwrap()),
+            "Pluto is kicked out"
+        );
+        assert_eq!(
+            render_error(&collect(&mut the_goose, Command::Kick("Pluto")).unwrap_err()),
+            "Pluto: unknown player"
+        );
+        assert_eq!(
+            render(&collect(&mut the_goose, Command::Rename("Pippo", "Paperino")).unwrap()),
+            "Pippo is now called Paperino"
+        );
+        assert_eq!(
+            render(&collect(&mut the_goose, Command::Remove("Paperino")).unwrap()),
+            "Paperino leaves the game"
+        );
+    }
+
+    #[test]
+    fn test_render_special_squares() {
+        let mut the_goose = SimpleTheGoose::new(vec![[1u32, 1], [2, 2], [1, 1]].into_iter());
+
+        the_goose.state.insert("Pippo", SamplePosition(4));
+        assert_eq!(
+            render(&collect(&mut the_goose, Command::RollAndMove("Pippo")).unwrap()),
+            "Pippo rolls 1, 1. Pippo moves from 4 to The Bridge. Pippo jumps to 12"
+        );
+
+        the_goose.state.insert("Pippo", SamplePosition(10));
+        assert_eq!(
+            render(&collect(&mut the_goose, Command::RollAndMove("Pippo")).unwrap()),
+            "Pippo rolls 2, 2. Pippo moves from 10 to 14, The Goose. \
+             Pippo moves again and goes to 18, The Goose. \
+             Pippo moves again and goes to 22"
+        );
+
+        the_goose.state.insert("Pippo", SamplePosition(15));
+        the_goose.state.insert("Pluto", SamplePosition(17));
+        assert_eq!(
+            render(&collect(&mut the_goose, Command::RollAndMove("Pippo")).unwrap()),
+            "Pippo rolls 1, 1. Pippo moves from 15 to 17. On 17 there is Pluto, who returns to 15"
+        );
+    }
+}
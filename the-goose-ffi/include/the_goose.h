@@ -0,0 +1,125 @@
+#ifndef THE_GOOSE_H
+#define THE_GOOSE_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum TheGooseStatus {
+  THE_GOOSE_STATUS_OK,
+  THE_GOOSE_STATUS_NULL_POINTER,
+  THE_GOOSE_STATUS_INVALID_NAME,
+  THE_GOOSE_STATUS_DUPLICATE_PLAYER,
+  THE_GOOSE_STATUS_PLAYER_NOT_FOUND,
+  THE_GOOSE_STATUS_INVALID_ROLL,
+  THE_GOOSE_STATUS_NOT_YOUR_TURN,
+  THE_GOOSE_STATUS_INVALID_POSITION,
+} TheGooseStatus;
+
+typedef enum TheGooseEventKind {
+  THE_GOOSE_EVENT_KIND_MOVED = 1,
+  THE_GOOSE_EVENT_KIND_MOVED_AGAIN = 2,
+  THE_GOOSE_EVENT_KIND_ROLL = 3,
+  THE_GOOSE_EVENT_KIND_BOUNCED = 4,
+  THE_GOOSE_EVENT_KIND_RETURN = 5,
+  THE_GOOSE_EVENT_KIND_WIN = 6,
+  THE_GOOSE_EVENT_KIND_PRANK = 7,
+  THE_GOOSE_EVENT_KIND_JUMP = 8,
+  THE_GOOSE_EVENT_KIND_GOOSE_TO_GOOSE = 9,
+  THE_GOOSE_EVENT_KIND_EXTRA_TURN = 10,
+  THE_GOOSE_EVENT_KIND_POSITION_SET = 11,
+  THE_GOOSE_EVENT_KIND_KICKED = 12,
+  THE_GOOSE_EVENT_KIND_RESET = 13,
+  THE_GOOSE_EVENT_KIND_REMATCH = 14,
+  THE_GOOSE_EVENT_KIND_PLAYER_JOINED = 15,
+  THE_GOOSE_EVENT_KIND_PLAYER_LEFT = 16,
+  THE_GOOSE_EVENT_KIND_PLAYER_RENAMED = 17,
+} TheGooseEventKind;
+
+typedef struct TheGooseGame TheGooseGame;
+
+typedef struct TheGooseEvent {
+  enum TheGooseEventKind kind;
+  const char *player;
+  const char *previous;
+  uint32_t first;
+  uint32_t second;
+} TheGooseEvent;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+struct TheGooseGame *the_goose_new(uint64_t seed);
+
+/**
+ * # Safety
+ *
+ * `game` must be null or a handle returned by `the_goose_new` that was not freed yet.
+ */
+void the_goose_free(struct TheGooseGame *game);
+
+/**
+ * # Safety
+ *
+ * `player` must be null or a NUL-terminated string.
+ */
+enum TheGooseStatus the_goose_add_player(struct TheGooseGame *game, const char *player);
+
+/**
+ * # Safety
+ *
+ * `player` must be null or a NUL-terminated string.
+ */
+enum TheGooseStatus the_goose_remove_player(struct TheGooseGame *game, const char *player);
+
+/**
+ * # Safety
+ *
+ * `player` and `name` must be null or NUL-terminated strings.
+ */
+enum TheGooseStatus the_goose_rename_player(struct TheGooseGame *game,
+                                            const char *player,
+                                            const char *name);
+
+/**
+ * # Safety
+ *
+ * `player` must be null or a NUL-terminated string.
+ */
+enum TheGooseStatus the_goose_move_player(struct TheGooseGame *game,
+                                          const char *player,
+                                          uint32_t first,
+                                          uint32_t second);
+
+/**
+ * # Safety
+ *
+ * `player` must be null or a NUL-terminated string.
+ */
+enum TheGooseStatus the_goose_roll_and_move(struct TheGooseGame *game, const char *player);
+
+/**
+ * # Safety
+ *
+ * `player` must be null or a NUL-terminated string.
+ */
+int32_t the_goose_position(const struct TheGooseGame *game, const char *player);
+
+size_t the_goose_events_len(const struct TheGooseGame *game);
+
+/**
+ * # Safety
+ *
+ * `event` must be null or point to writable memory for a `TheGooseEvent`. The player
+ * names it receives stay valid until the next command on `game`.
+ */
+bool the_goose_event(const struct TheGooseGame *game, size_t index, struct TheGooseEvent *event);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* THE_GOOSE_H */
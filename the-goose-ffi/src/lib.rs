@@ -0,0 +1,395 @@
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use sample::{collect, SampleDie, SamplePosition, SampleTheGoose};
+use the_goose::{Command, Error, Event, EventKind};
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TheGooseStatus {
+    Ok,
+    NullPointer,
+    InvalidName,
+    DuplicatePlayer,
+    PlayerNotFound,
+    InvalidRoll,
+    NotYourTurn,
+    InvalidPosition,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TheGooseEventKind {
+    // 0 was the retired player list snapshot.
+    Moved = 1,
+    MovedAgain = 2,
+    Roll = 3,
+    Bounced = 4,
+    Return = 5,
+    Win = 6,
+    Prank = 7,
+    Jump = 8,
+    GooseToGoose = 9,
+    ExtraTurn = 10,
+    PositionSet = 11,
+    Kicked = 12,
+    Reset = 13,
+    Rematch = 14,
+    PlayerJoined = 15,
+    PlayerLeft = 16,
+    PlayerRenamed = 17,
+}
+
+impl From<EventKind> for TheGooseEventKind {
+    fn from(kind: EventKind) -> Self {
+        match kind {
+            EventKind::PlayerJoined => TheGooseEventKind::PlayerJoined,
+            EventKind::PlayerLeft => TheGooseEventKind::PlayerLeft,
+            EventKind::PlayerRenamed => TheGooseEventKind::PlayerRenamed,
+            EventKind::Moved => TheGooseEventKind::Moved,
+            EventKind::MovedAgain => TheGooseEventKind::MovedAgain,
+            EventKind::Roll => TheGooseEventKind::Roll,
+            EventKind::Bounced => TheGooseEventKind::Bounced,
+            EventKind::Return => TheGooseEventKind::Return,
+            EventKind::Win => TheGooseEventKind::Win,
+            EventKind::Prank => TheGooseEventKind::Prank,
+            EventKind::Jump => TheGooseEventKind::Jump,
+            EventKind::GooseToGoose => TheGooseEventKind::GooseToGoose,
+            EventKind::ExtraTurn => TheGooseEventKind::ExtraTurn,
+            EventKind::PositionSet => TheGooseEventKind::PositionSet,
+            EventKind::Kicked => TheGooseEventKind::Kicked,
+            EventKind::Reset => TheGooseEventKind::Reset,
+            EventKind::Rematch => TheGooseEventKind::Rematch,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct TheGooseEvent {
+    pub kind: TheGooseEventKind,
+    pub player: *const c_char,
+    pub previous: *const c_char,
+    pub first: u32,
+    pub second: u32,
+}
+
+pub struct TheGooseGame {
+    the_goose: SampleTheGoose<String, SamplePosition>,
+    events: Vec<TheGooseEvent>,
+    names: Vec<CString>,
+}
+
+impl TheGooseGame {
+    fn execute(&mut self, command: Command<String, [u32; 2]>) -> TheGooseStatus {
+        self.events.clear();
+        self.names.clear();
+
+        let events = match collect(&mut self.the_goose, command) {
+            Ok(events) => events,
+            Err(Error::Inner(e) | Error::Events(e)) => match e {},
+            Err(Error::DuplicatePlayer(_)) => return TheGooseStatus::DuplicatePlayer,
+            Err(Error::PlayerNotFound(_)) => return TheGooseStatus::PlayerNotFound,
+            Err(Error::InvalidRoll(..)) => return TheGooseStatus::InvalidRoll,
+            Err(Error::NotYourTurn(_)) => return TheGooseStatus::NotYourTurn,
+            Err(Error::InvalidPosition(..)) => return TheGooseStatus::InvalidPosition,
+        };
+
+        for event in events.iter() {
+            let player = self.intern(event.player());
+            let previous = match event {
+                Event::PlayerRenamed(previous, _) => self.intern(Some(previous)),
+                _ => ptr::null(),
+            };
+            let (first, second) = match event {
+                Event::Roll(_, [first, second]) => (*first, *second),
+                Event::Moved(_, from, to, _)
+                | Event::MovedAgain(_, from, to, _)
+                | Event::Prank(_, from, to)
+                | Event::GooseToGoose(_, from, to, _) => ((*from).into(), (*to).into()),
+                Event::Return(_, to) | Event::Jump(_, to, _) | Event::PositionSet(_, to) => {
+                    ((*to).into(), 0)
+                }
+                Event::PlayerJoined(_)
+                | Event::PlayerLeft(_)
+                | Event::PlayerRenamed(..)
+                | Event::Bounced(_)
+                | Event::Win(_)
+                | Event::ExtraTurn(_)
+                | Event::Kicked(_)
+                | Event::Reset
+                | Event::Rematch => (0, 0),
+            };
+
+            self.events.push(TheGooseEvent {
+                kind: event.kind().into(),
+                player,
+                previous,
+                first,
+                second,
+            });
+        }
+
+        TheGooseStatus::Ok
+    }
+
+    fn intern(&mut self, name: Option<&String>) -> *const c_char {
+        match name {
+            Some(name) => {
+                self.names.push(CString::new(name.as_str()).unwrap());
+                self.names.last().unwrap().as_ptr()
+            }
+            None => ptr::null(),
+        }
+    }
+}
+
+unsafe fn name(name: *const c_char) -> Result<String, TheGooseStatus> {
+    if name.is_null() {
+        return Err(TheGooseStatus::NullPointer);
+    }
+
+    CStr::from_ptr(name)
+        .to_str()
+        .map(str::to_string)
+        .map_err(|_| TheGooseStatus::InvalidName)
+}
+
+unsafe fn execute(
+    game: Option<&mut TheGooseGame>,
+    player: *const c_char,
+    command: impl FnOnce(String) -> Command<String, [u32; 2]>,
+) -> TheGooseStatus {
+    let Some(game) = game else {
+        return TheGooseStatus::NullPointer;
+    };
+
+    match name(player) {
+        Ok(player) => game.execute(command(player)),
+        Err(status) => status,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn the_goose_new(seed: u64) -> *mut TheGooseGame {
+    Box::into_raw(Box::new(TheGooseGame {
+        the_goose: SampleTheGoose::with_die(SampleDie::new(1..=6, seed)),
+        events: Vec::new(),
+        names: Vec::new(),
+    }))
+}
+
+/// # Safety
+///
+/// `game` must be null or a handle returned by `the_goose_new` that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn the_goose_free(game: *mut TheGooseGame) {
+    if !game.is_null() {
+        drop(Box::from_raw(game));
+    }
+}
+
+/// # Safety
+///
+/// `player` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn the_goose_add_player(
+    game: Option<&mut TheGooseGame>,
+    player: *const c_char,
+) -> TheGooseStatus {
+    execute(game, player, Command::Add)
+}
+
+/// # Safety
+///
+/// `player` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn the_goose_remove_player(
+    game: Option<&mut TheGooseGame>,
+    player: *const c_char,
+) -> TheGooseStatus {
+    execute(game, player, Command::Remove)
+}
+
+/// # Safety
+///
+/// `player` and `name` must be null or NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn the_goose_rename_player(
+    game: Option<&mut TheGooseGame>,
+    player: *const c_char,
+    name: *const c_char,
+) -> TheGooseStatus {
+    match self::name(name) {
+        Ok(name) => execute(game, player, |player| Command::Rename(player, name)),
+        Err(status) => status,
+    }
+}
+
+/// # Safety
+///
+/// `player` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn the_goose_move_player(
+    game: Option<&mut TheGooseGame>,
+    player: *const c_char,
+    first: u32,
+    second: u32,
+) -> TheGooseStatus {
+    execute(game, player, |player| {
+        Command::Move(player, [first, second])
+    })
+}
+
+/// # Safety
+///
+/// `player` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn the_goose_roll_and_move(
+    game: Option<&mut TheGooseGame>,
+    player: *const c_char,
+) -> TheGooseStatus {
+    execute(game, player, Command::RollAndMove)
+}
+
+/// # Safety
+///
+/// `player` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn the_goose_position(
+    game: Option<&TheGooseGame>,
+    player: *const c_char,
+) -> i32 {
+    match (game, name(player)) {
+        (Some(game), Ok(player)) => game
+            .the_goose
+            .get(&player)
+            .map_or(-1, |position| u32::from(*position) as i32),
+        _ => -1,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn the_goose_events_len(game: Option<&TheGooseGame>) -> usize {
+    game.map_or(0, |game| game.events.len())
+}
+
+/// # Safety
+///
+/// `event` must be null or point to writable memory for a `TheGooseEvent`. The player
+/// names it receives stay valid until the next command on `game`.
+#[no_mangle]
+pub unsafe extern "C" fn the_goose_event(
+    game: Option<&TheGooseGame>,
+    index: usize,
+    event: *mut TheGooseEvent,
+) -> bool {
+    match (
+        game.and_then(|game| game.events.get(index)),
+        event.is_null(),
+    ) {
+        (Some(e), false) => {
+            event.write(*e);
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn event(game: &TheGooseGame, index: usize) -> Option<(TheGooseEventKind, String, u32, u32)> {
+        let mut event = TheGooseEvent {
+            kind: TheGooseEventKind::Win,
+            player: ptr::null(),
+            previous: ptr::null(),
+            first: 0,
+            second: 0,
+        };
+
+        unsafe {
+            the_goose_event(Some(game), index, &mut event).then(|| {
+                let player = if event.player.is_null() {
+                    String::new()
+                } else {
+                    CStr::from_ptr(event.player).to_str().unwrap().to_string()
+                };
+
+                (event.kind, player, event.first, event.second)
+            })
+        }
+    }
+
+    #[test]
+    fn test_game() {
+        let game = the_goose_new(1);
+        let pippo = c"Pippo".as_ptr();
+
+        unsafe {
+            assert_eq!(
+                the_goose_add_player(game.as_mut(), pippo),
+                TheGooseStatus::Ok
+            );
+            assert_eq!(
+                event(&*game, 0),
+                Some((TheGooseEventKind::PlayerJoined, "Pippo".to_string(), 0, 0))
+            );
+
+            assert_eq!(
+                the_goose_add_player(game.as_mut(), pippo),
+                TheGooseStatus::DuplicatePlayer
+            );
+            assert_eq!(the_goose_events_len(game.as_ref()), 0);
+
+            assert_eq!(
+                the_goose_move_player(game.as_mut(), pippo, 2, 3),
+                TheGooseStatus::Ok
+            );
+            assert_eq!(the_goose_events_len(game.as_ref()), 3);
+            assert_eq!(
+                event(&*game, 2),
+                Some((TheGooseEventKind::MovedAgain, "Pippo".to_string(), 5, 10))
+            );
+            assert_eq!(event(&*game, 3), None);
+            assert_eq!(the_goose_position(game.as_ref(), pippo), 10);
+
+            assert_eq!(
+                the_goose_move_player(game.as_mut(), pippo, 2, 9),
+                TheGooseStatus::InvalidRoll
+            );
+            assert_eq!(
+                the_goose_roll_and_move(game.as_mut(), c"Pluto".as_ptr()),
+                TheGooseStatus::PlayerNotFound
+            );
+            assert_eq!(
+                the_goose_add_player(game.as_mut(), ptr::null()),
+                TheGooseStatus::NullPointer
+            );
+            assert_eq!(
+                the_goose_add_player(None, pippo),
+                TheGooseStatus::NullPointer
+            );
+
+            assert_eq!(
+                the_goose_rename_player(game.as_mut(), pippo, c"Pluto".as_ptr()),
+                TheGooseStatus::Ok
+            );
+            assert_eq!(
+                event(&*game, 0),
+                Some((TheGooseEventKind::PlayerRenamed, "Pluto".to_string(), 0, 0))
+            );
+            assert_eq!(
+                CStr::from_ptr((&(*game).events)[0].previous).to_str(),
+                Ok("Pippo")
+            );
+            assert_eq!(
+                the_goose_rename_player(game.as_mut(), pippo, ptr::null()),
+                TheGooseStatus::NullPointer
+            );
+
+            the_goose_free(game);
+        }
+    }
+}
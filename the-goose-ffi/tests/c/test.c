@@ -0,0 +1,61 @@
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "the_goose.h"
+
+static void print_events(const TheGooseGame *game) {
+  TheGooseEvent event;
+
+  for (size_t i = 0; the_goose_event(game, i, &event); i++) {
+    printf("%d %s %u %u\n", event.kind, event.player ? event.player : "-", event.first,
+           event.second);
+  }
+}
+
+int main(void) {
+  TheGooseGame *game = the_goose_new(42);
+  TheGooseEvent event;
+
+  assert(the_goose_add_player(game, "Pippo") == THE_GOOSE_STATUS_OK);
+  assert(the_goose_add_player(game, "Pluto") == THE_GOOSE_STATUS_OK);
+  assert(the_goose_events_len(game) == 1);
+  assert(the_goose_event(game, 0, &event));
+  assert(event.kind == THE_GOOSE_EVENT_KIND_PLAYER_JOINED && strcmp(event.player, "Pluto") == 0);
+
+  assert(the_goose_add_player(game, "Pippo") == THE_GOOSE_STATUS_DUPLICATE_PLAYER);
+  assert(the_goose_events_len(game) == 0);
+
+  assert(the_goose_move_player(game, "Pluto", 4, 3) == THE_GOOSE_STATUS_OK);
+  assert(the_goose_move_player(game, "Pippo", 4, 3) == THE_GOOSE_STATUS_OK);
+  print_events(game);
+  assert(the_goose_events_len(game) == 3);
+  assert(the_goose_event(game, 2, &event));
+  assert(event.kind == THE_GOOSE_EVENT_KIND_PRANK && strcmp(event.player, "Pluto") == 0);
+  assert(event.first == 7 && event.second == 0);
+  assert(!the_goose_event(game, 3, &event));
+
+  assert(the_goose_position(game, "Pippo") == 7);
+  assert(the_goose_position(game, "Pluto") == 0);
+  assert(the_goose_position(game, "Paperino") == -1);
+
+  assert(the_goose_move_player(game, "Pippo", 1, 1) == THE_GOOSE_STATUS_NOT_YOUR_TURN);
+  assert(the_goose_move_player(game, "Pluto", 7, 1) == THE_GOOSE_STATUS_INVALID_ROLL);
+  assert(the_goose_roll_and_move(game, "Paperino") == THE_GOOSE_STATUS_PLAYER_NOT_FOUND);
+  assert(the_goose_roll_and_move(game, "Pluto") == THE_GOOSE_STATUS_OK);
+  assert(the_goose_event(game, 0, &event) && event.kind == THE_GOOSE_EVENT_KIND_ROLL);
+  assert(event.first >= 1 && event.first <= 6 && event.second >= 1 && event.second <= 6);
+
+  assert(the_goose_rename_player(game, "Pluto", "Topolino") == THE_GOOSE_STATUS_OK);
+  assert(the_goose_event(game, 0, &event) && event.kind == THE_GOOSE_EVENT_KIND_PLAYER_RENAMED);
+  assert(strcmp(event.player, "Topolino") == 0 && strcmp(event.previous, "Pluto") == 0);
+
+  assert(the_goose_remove_player(game, "Pippo") == THE_GOOSE_STATUS_OK);
+  assert(the_goose_add_player(NULL, "Pippo") == THE_GOOSE_STATUS_NULL_POINTER);
+  assert(the_goose_add_player(game, "\xff") == THE_GOOSE_STATUS_INVALID_NAME);
+
+  the_goose_free(game);
+  the_goose_free(NULL);
+
+  return 0;
+}
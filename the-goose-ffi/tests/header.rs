@@ -0,0 +1,27 @@
+use std::path::Path;
+
+#[test]
+fn test_header_is_up_to_date() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let path = crate_dir.join("include/the_goose.h");
+
+    let mut header = Vec::new();
+    cbindgen::Builder::new()
+        .with_crate(crate_dir)
+        .with_config(cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap())
+        .generate()
+        .unwrap()
+        .write(&mut header);
+    let header = String::from_utf8(header).unwrap();
+
+    if std::env::var_os("THE_GOOSE_BLESS").is_some() {
+        std::fs::write(&path, &header).unwrap();
+    }
+
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        header,
+        "run with THE_GOOSE_BLESS=1 to regenerate {}",
+        path.display()
+    );
+}
@@ -0,0 +1,592 @@
+#![no_std]
+
+use core::ops::{Deref, RangeInclusive};
+
+use heapless::Vec;
+
+use the_goose::{
+    AddPosition, Event, Events, ExtraTurns, GooseRule, Position, PositionType, State, TheGoose,
+    Turn,
+};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct CapacityExceeded;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Square(u8);
+
+impl Square {
+    pub const END: u8 = 63;
+}
+
+impl Position<u8> for Square {
+    fn add(self, s: u8) -> AddPosition<Self> {
+        let target = u16::from(self.0) + u16::from(s);
+        let end = u16::from(Square::END);
+
+        if target > end {
+            AddPosition::Bounced(
+                Square((end * 2).saturating_sub(target) as u8),
+                Square(Square::END),
+            )
+        } else {
+            AddPosition::Normal(Square(target as u8))
+        }
+    }
+
+    fn get_type(&self) -> PositionType {
+        match self.0 {
+            6 => PositionType::TheBridge,
+            5 | 9 | 14 | 18 | 23 | 27 => PositionType::TheGoose,
+            Square::END => PositionType::End,
+            _ => PositionType::Normal,
+        }
+    }
+
+    fn end() -> Self {
+        Square(Square::END)
+    }
+
+    fn next_goose(&self) -> Option<Self> {
+        (self.0 + 1..Square::END)
+            .map(Square)
+            .find(|square| matches!(square.get_type(), PositionType::TheGoose))
+    }
+
+    fn is_dice(&self) -> bool {
+        matches!(self.0, 26 | 53)
+    }
+}
+
+impl From<u32> for Square {
+    fn from(value: u32) -> Self {
+        Square(value.min(u32::from(Square::END)) as u8)
+    }
+}
+
+impl From<Square> for u32 {
+    fn from(value: Square) -> Self {
+        value.0.into()
+    }
+}
+
+impl From<Square> for u8 {
+    fn from(value: Square) -> Self {
+        value.0
+    }
+}
+
+type Positions<Player, P, const PLAYERS: usize> = Vec<(Player, P), PLAYERS>;
+
+pub struct HeaplessState<Player, P, const PLAYERS: usize> {
+    positions: Positions<Player, P, PLAYERS>,
+    turn: Turn<Player>,
+    snapshot: Option<(Positions<Player, P, PLAYERS>, Turn<Player>)>,
+}
+
+impl<Player, P, const PLAYERS: usize> HeaplessState<Player, P, PLAYERS> {
+    pub const fn new() -> Self {
+        HeaplessState {
+            positions: Vec::new(),
+            turn: Turn {
+                player: None,
+                extra_turns: 0,
+            },
+            snapshot: None,
+        }
+    }
+}
+
+impl<Player, P, const PLAYERS: usize> Default for HeaplessState<Player, P, PLAYERS> {
+    fn default() -> Self {
+        HeaplessState::new()
+    }
+}
+
+impl<Player, P, const PLAYERS: usize> State<Player, P, Vec<Player, PLAYERS>>
+    for HeaplessState<Player, P, PLAYERS>
+where
+    Player: PartialEq + Clone,
+    P: PartialEq + Copy + From<u32>,
+{
+    type Error = CapacityExceeded;
+
+    fn get_player_position(&self, player: &Player) -> Result<Option<P>, Self::Error> {
+        Ok(self
+            .positions
+            .iter()
+            .find_map(|(p, position)| if p == player { Some(*position) } else { None }))
+    }
+
+    fn add_player(&mut self, player: Player) -> Result<(), Self::Error> {
+        self.positions
+            .push((player, P::from(0)))
+            .map_err(|_| CapacityExceeded)
+    }
+
+    fn remove_player(&mut self, player: &Player) -> Result<(), Self::Error> {
+        if let Some(index) = self.positions.iter().position(|(p, _)| p == player) {
+            self.positions.remove(index);
+        }
+
+        Ok(())
+    }
+
+    fn find_players_by_position(&self, position: &P) -> Result<Vec<Player, PLAYERS>, Self::Error> {
+        Ok(self
+            .positions
+            .iter()
+            .filter_map(|(p, pos)| if pos == position { Some(p) } else { None })
+            .cloned()
+            .collect())
+    }
+
+    fn players(&self) -> Result<Vec<Player, PLAYERS>, Self::Error> {
+        Ok(self.positions.iter().map(|(p, _)| p).cloned().collect())
+    }
+
+    fn update_player_position(&mut self, player: &Player, position: &P) -> Result<(), Self::Error> {
+        if let Some((_, p)) = self.positions.iter_mut().find(|(p, _)| p == player) {
+            *p = *position;
+        }
+
+        Ok(())
+    }
+
+    fn begin(&mut self) -> Result<(), Self::Error> {
+        self.snapshot = Some((self.positions.clone(), self.turn.clone()));
+
+        Ok(())
+    }
+
+    fn commit(&mut self) -> Result<(), Self::Error> {
+        self.snapshot = None;
+
+        Ok(())
+    }
+
+    fn rollback(&mut self) -> Result<(), Self::Error> {
+        if let Some((positions, turn)) = self.snapshot.take() {
+            self.positions = positions;
+            self.turn = turn;
+        }
+
+        Ok(())
+    }
+
+    fn turn(&self) -> Result<Turn<Player>, Self::Error> {
+        Ok(self.turn.clone())
+    }
+
+    fn update_turn(&mut self, turn: Turn<Player>) -> Result<(), Self::Error> {
+        self.turn = turn;
+
+        Ok(())
+    }
+}
+
+pub struct HeaplessEvents<Player, P, R, const EVENTS: usize>(
+    Vec<Event<Player, P, R>, EVENTS>,
+    usize,
+);
+
+impl<Player, P, R, const EVENTS: usize> Default for HeaplessEvents<Player, P, R, EVENTS> {
+    fn default() -> Self {
+        HeaplessEvents(Vec::new(), 0)
+    }
+}
+
+impl<Player, P, R, const EVENTS: usize> Deref for HeaplessEvents<Player, P, R, EVENTS> {
+    type Target = [Event<Player, P, R>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<Player, P, R, const EVENTS: usize> Events<Player, P, R>
+    for HeaplessEvents<Player, P, R, EVENTS>
+{
+    type Error = CapacityExceeded;
+
+    fn notify(&mut self, event: Event<Player, P, R>) -> Result<(), Self::Error> {
+        self.0.push(event).map_err(|_| CapacityExceeded)
+    }
+
+    fn begin(&mut self) -> Result<(), Self::Error> {
+        self.1 = self.0.len();
+
+        Ok(())
+    }
+
+    fn rollback(&mut self) -> Result<(), Self::Error> {
+        self.0.truncate(self.1);
+
+        Ok(())
+    }
+}
+
+pub struct XorShiftDie {
+    faces: RangeInclusive<u8>,
+    seed: u32,
+}
+
+impl XorShiftDie {
+    pub const fn new(faces: RangeInclusive<u8>, seed: u32) -> Self {
+        XorShiftDie {
+            faces,
+            seed: if seed == 0 { 0x9e37_79b9 } else { seed },
+        }
+    }
+
+    /// Whether `face` is one of the die's faces and no longer than the board.
+    pub fn contains(&self, face: &u8) -> bool {
+        self.faces.contains(face) && *face <= Square::END
+    }
+
+    pub fn roll(&mut self) -> u8 {
+        // xorshift32
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+
+        let (first, last) = (*self.faces.start(), *self.faces.end());
+        first + (self.seed % (u32::from(last - first) + 1)) as u8
+    }
+}
+
+pub struct HeaplessTheGoose<Player, const PLAYERS: usize> {
+    state: HeaplessState<Player, Square, PLAYERS>,
+    die: XorShiftDie,
+    goose_rule: GooseRule,
+    extra_turns: ExtraTurns,
+}
+
+impl<Player, const PLAYERS: usize> HeaplessTheGoose<Player, PLAYERS> {
+    pub const fn new(die: XorShiftDie) -> Self {
+        HeaplessTheGoose {
+            state: HeaplessState::new(),
+            die,
+            goose_rule: GooseRule::MoveAgain,
+            extra_turns: ExtraTurns {
+                doubles: false,
+                dice: false,
+                cap: 3,
+            },
+        }
+    }
+
+    pub fn with_goose_rule(mut self, goose_rule: GooseRule) -> Self {
+        self.goose_rule = goose_rule;
+        self
+    }
+
+    pub fn with_extra_turns(mut self, extra_turns: ExtraTurns) -> Self {
+        self.extra_turns = extra_turns;
+        self
+    }
+}
+
+impl<Player, const PLAYERS: usize> TheGoose<Player, Square, [u8; 2], Vec<Player, PLAYERS>>
+    for HeaplessTheGoose<Player, PLAYERS>
+where
+    Player: PartialEq + Clone,
+{
+    type State = HeaplessState<Player, Square, PLAYERS>;
+
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+
+    fn roll_dice(&mut self) -> [u8; 2] {
+        [self.die.roll(), self.die.roll()]
+    }
+
+    fn is_valid_face(&self, face: &u8) -> bool {
+        self.die.contains(face)
+    }
+
+    fn goose_rule(&self) -> GooseRule {
+        self.goose_rule
+    }
+
+    fn extra_turns(&self) -> ExtraTurns {
+        self.extra_turns
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use the_goose::{Cause, Command, Error, Landing};
+
+    fn players<const N: usize>(players: &[&'static str]) -> Vec<&'static str, N> {
+        players.iter().copied().collect()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn execute<const N: usize>(
+        the_goose: &mut HeaplessTheGoose<&'static str, N>,
+        command: Command<&'static str, [u8; 2]>,
+    ) -> Result<
+        HeaplessEvents<&'static str, Square, [u8; 2], 8>,
+        Error<&'static str, u8, CapacityExceeded, CapacityExceeded>,
+    > {
+        let mut events = HeaplessEvents::default();
+
+        the_goose.execute(command, &mut events).map(|()| events)
+    }
+
+    #[test]
+    fn test_players_capacity() {
+        let mut the_goose = HeaplessTheGoose::<_, 2>::new(XorShiftDie::new(1..=6, 1));
+
+        assert!(matches!(
+            execute(&mut the_goose, Command::Add("Pippo")).as_deref(),
+            Ok([Event::PlayerJoined("Pippo")])
+        ));
+        assert!(matches!(
+            execute(&mut the_goose, Command::Add("Pluto")).as_deref(),
+            Ok([Event::PlayerJoined("Pluto")])
+        ));
+        assert_eq!(
+            the_goose.state().players(),
+            Ok(players::<2>(&["Pippo", "Pluto"]))
+        );
+        assert!(matches!(
+            execute(&mut the_goose, Command::Add("Paperino")),
+            Err(Error::Inner(CapacityExceeded))
+        ));
+        assert!(matches!(
+            execute(&mut the_goose, Command::Remove("Pippo")).as_deref(),
+            Ok([Event::PlayerLeft("Pippo")])
+        ));
+        assert_eq!(the_goose.state().players(), Ok(players::<2>(&["Pluto"])));
+        assert!(execute(&mut the_goose, Command::Add("Paperino")).is_ok());
+    }
+
+    #[test]
+    fn test_events_capacity() {
+        let mut events = HeaplessEvents::<&str, Square, [u8; 2], 2>::default();
+
+        assert_eq!(events.notify(Event::Win("Pippo")), Ok(()));
+        assert_eq!(events.notify(Event::Bounced("Pippo")), Ok(()));
+        assert_eq!(events.notify(Event::Win("Pluto")), Err(CapacityExceeded));
+        assert_eq!(events.len(), 2);
+    }
+
+    #[test]
+    fn test_moves() {
+        let mut the_goose = HeaplessTheGoose::<_, 2>::new(XorShiftDie::new(1..=6, 1));
+
+        execute(&mut the_goose, Command::Add("Pippo")).ok();
+        execute(&mut the_goose, Command::Add("Pluto")).ok();
+        the_goose
+            .state_mut()
+            .update_player_position(&"Pippo", &Square(10))
+            .ok();
+        the_goose
+            .state_mut()
+            .update_player_position(&"Pluto", &Square(22))
+            .ok();
+
+        assert!(matches!(
+            execute(&mut the_goose, Command::Move("Pippo", [2, 2])).as_deref(),
+            Ok([
+                Event::Roll("Pippo", [2, 2]),
+                Event::Moved(
+                    "Pippo",
+                    Square(10),
+                    Square(14),
+                    Landing {
+                        square: PositionType::TheGoose,
+                        cause: Cause::Roll,
+                        sequence: 0
+                    }
+                ),
+                Event::MovedAgain(
+                    "Pippo",
+                    Square(14),
+                    Square(18),
+                    Landing {
+                        square: PositionType::TheGoose,
+                        cause: Cause::TheGoose,
+                        sequence: 1
+                    }
+                ),
+                Event::MovedAgain(
+                    "Pippo",
+                    Square(18),
+                    Square(22),
+                    Landing {
+                        square: PositionType::Normal,
+                        cause: Cause::TheGoose,
+                        sequence: 2
+                    }
+                ),
+                Event::Prank("Pluto", Square(22), Square(10)),
+            ])
+        ));
+
+        the_goose
+            .state_mut()
+            .update_player_position(&"Pippo", &Square(60))
+            .ok();
+        the_goose.state_mut().update_turn(Turn::default()).ok();
+        assert!(matches!(
+            execute(&mut the_goose, Command::Move("Pippo", [3, 2])).as_deref(),
+            Ok([
+                Event::Roll("Pippo", [3, 2]),
+                Event::Moved(
+                    "Pippo",
+                    Square(60),
+                    Square(63),
+                    Landing {
+                        square: PositionType::Normal,
+                        cause: Cause::Roll,
+                        sequence: 0
+                    }
+                ),
+                Event::Bounced("Pippo"),
+                Event::Return("Pippo", Square(61)),
+            ])
+        ));
+
+        assert!(matches!(
+            execute(&mut the_goose, Command::Move("Pluto", [7, 1])),
+            Err(Error::InvalidRoll("Pluto", 7))
+        ));
+    }
+
+    #[test]
+    fn test_turn_order() {
+        let mut the_goose = HeaplessTheGoose::<_, 2>::new(XorShiftDie::new(1..=6, 1));
+
+        execute(&mut the_goose, Command::Add("Pippo")).ok();
+        execute(&mut the_goose, Command::Add("Pluto")).ok();
+
+        execute(&mut the_goose, Command::Move("Pippo", [1, 2])).unwrap();
+        assert_eq!(the_goose.state().turn().unwrap().player, Some("Pluto"));
+        assert!(matches!(
+            execute(&mut the_goose, Command::Move("Pippo", [1, 2])),
+            Err(Error::NotYourTurn("Pippo"))
+        ));
+
+        execute(&mut the_goose, Command::Move("Pluto", [1, 2])).unwrap();
+        assert_eq!(the_goose.state().turn().unwrap().player, Some("Pippo"));
+
+        let mut events = HeaplessEvents::<_, _, _, 1>::default();
+        assert!(the_goose
+            .execute(Command::Move("Pippo", [1, 2]), &mut events)
+            .is_err());
+        assert_eq!(the_goose.state().turn().unwrap().player, Some("Pippo"));
+    }
+
+    #[test]
+    fn test_goose_to_goose() {
+        let mut the_goose = HeaplessTheGoose::<_, 2>::new(XorShiftDie::new(1..=6, 1))
+            .with_goose_rule(GooseRule::GooseToGoose);
+
+        execute(&mut the_goose, Command::Add("Pippo")).ok();
+        execute(&mut the_goose, Command::Add("Pluto")).ok();
+
+        let events = execute(&mut the_goose, Command::Move("Pippo", [2, 3])).unwrap();
+        assert!(matches!(events.last(), Some(Event::ExtraTurn("Pippo"))));
+        assert_eq!(
+            the_goose.state().get_player_position(&"Pippo"),
+            Ok(Some(Square(9)))
+        );
+        assert_eq!(the_goose.state().turn().unwrap().player, Some("Pippo"));
+    }
+
+    #[test]
+    fn test_set_position() {
+        let mut the_goose = HeaplessTheGoose::<_, 1>::new(XorShiftDie::new(1..=6, 1));
+
+        execute(&mut the_goose, Command::Add("Pippo")).ok();
+
+        assert!(matches!(
+            execute(&mut the_goose, Command::SetPosition("Pippo", 63)).as_deref(),
+            Ok([Event::PositionSet("Pippo", Square(63))])
+        ));
+        assert!(matches!(
+            execute(&mut the_goose, Command::SetPosition("Pippo", 200)),
+            Err(Error::InvalidPosition("Pippo", 200))
+        ));
+        assert_eq!(
+            the_goose.state().get_player_position(&"Pippo"),
+            Ok(Some(Square(63)))
+        );
+    }
+
+    #[test]
+    fn test_events_capacity_propagates() {
+        let mut the_goose = HeaplessTheGoose::<_, 2>::new(XorShiftDie::new(1..=6, 1));
+        let mut events = HeaplessEvents::<_, _, _, 1>::default();
+
+        the_goose.execute(Command::Add("Pippo"), &mut events).ok();
+
+        assert_eq!(
+            the_goose.execute(Command::Add("Pluto"), &mut events),
+            Err(Error::Events(CapacityExceeded))
+        );
+        assert_eq!(the_goose.state().get_player_position(&"Pluto"), Ok(None));
+
+        let mut events = HeaplessEvents::<_, _, _, 1>::default();
+        assert_eq!(
+            the_goose.execute(Command::Move("Pippo", [1, 2]), &mut events),
+            Err(Error::Events(CapacityExceeded))
+        );
+        assert_eq!(
+            the_goose.state().get_player_position(&"Pippo"),
+            Ok(Some(Square(0)))
+        );
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_roll_and_move() {
+        let mut the_goose = HeaplessTheGoose::<_, 1>::new(XorShiftDie::new(1..=6, 1));
+
+        execute(&mut the_goose, Command::Add("Pippo")).ok();
+
+        for _ in 0..100 {
+            let events = execute(&mut the_goose, Command::RollAndMove("Pippo")).unwrap();
+            assert!(matches!(
+                events.first(),
+                Some(Event::Roll("Pippo", [1..=6, 1..=6]))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_bounce_matches_sample() {
+        fn squares<P>(add: AddPosition<P>, into: impl Fn(P) -> u32) -> (u32, Option<u32>) {
+            match add {
+                AddPosition::Bounced(bounced, end) => (into(end), Some(into(bounced))),
+                AddPosition::Normal(end) => (into(end), None),
+            }
+        }
+
+        for square in 0..=Square::END {
+            for steps in 0..=u8::MAX {
+                assert_eq!(
+                    squares(Square(square).add(steps), |s| u8::from(s).into()),
+                    squares(
+                        sample::SamplePosition::from(u32::from(square)).add(steps.into()),
+                        u32::from
+                    ),
+                );
+            }
+        }
+
+        assert!(matches!(
+            Square(60).add(u8::MAX),
+            AddPosition::Bounced(Square(0), Square(Square::END))
+        ));
+    }
+}
@@ -0,0 +1,225 @@
+use std::convert::Infallible;
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use sample::text::render_error;
+use sample::{collect, SampleDie, SamplePosition, SampleTheGoose};
+use the_goose::{Command, Error, Events, Landing, TheGoose};
+
+type GameEvent = the_goose::Event<String, SamplePosition, [u32; 2]>;
+
+create_exception!(the_goose, DuplicatePlayer, PyValueError);
+create_exception!(the_goose, PlayerNotFound, PyValueError);
+create_exception!(the_goose, InvalidRoll, PyValueError);
+create_exception!(the_goose, NotYourTurn, PyValueError);
+create_exception!(the_goose, InvalidPosition, PyValueError);
+
+#[pyclass(frozen, eq, get_all, skip_from_py_object)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Event {
+    kind: String,
+    player: Option<String>,
+    previous: Option<String>,
+    roll: Option<(u32, u32)>,
+    start: Option<u32>,
+    end: Option<u32>,
+    square: Option<String>,
+    cause: Option<String>,
+    sequence: Option<u32>,
+}
+
+#[pymethods]
+impl Event {
+    fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+impl Event {
+    fn landing(&mut self, landing: Landing) {
+        self.square = Some(format!("{:?}", landing.square));
+        self.cause = Some(format!("{:?}", landing.cause));
+        self.sequence = Some(landing.sequence);
+    }
+}
+
+impl From<GameEvent> for Event {
+    fn from(event: GameEvent) -> Self {
+        use the_goose::Event::*;
+
+        let mut py = Event {
+            kind: format!("{:?}", event.kind()),
+            player: event.player().cloned(),
+            previous: None,
+            roll: None,
+            start: None,
+            end: None,
+            square: None,
+            cause: None,
+            sequence: None,
+        };
+
+        match event {
+            PlayerRenamed(previous, _) => py.previous = Some(previous),
+            Roll(_, [first, second]) => py.roll = Some((first, second)),
+            Moved(_, start, end, landing)
+            | MovedAgain(_, start, end, landing)
+            | GooseToGoose(_, start, end, landing) => {
+                py.start = Some(start.into());
+                py.end = Some(end.into());
+                py.landing(landing);
+            }
+            Prank(_, start, end) => {
+                py.start = Some(start.into());
+                py.end = Some(end.into());
+            }
+            Jump(_, end, landing) => {
+                py.end = Some(end.into());
+                py.landing(landing);
+            }
+            Return(_, end) | PositionSet(_, end) => py.end = Some(end.into()),
+            PlayerJoined(_) | PlayerLeft(_) | Bounced(_) | Win(_) | ExtraTurn(_) | Kicked(_)
+            | Reset | Rematch => {}
+        }
+
+        py
+    }
+}
+
+#[pyclass]
+pub struct Game {
+    the_goose: SampleTheGoose<String, SamplePosition>,
+}
+
+impl Game {
+    fn execute(&mut self, command: Command<String, [u32; 2]>) -> PyResult<Vec<Event>> {
+        match collect(&mut self.the_goose, command) {
+            Ok(events) => Ok(events.iter().cloned().map(Event::from).collect()),
+            Err(e) => {
+                let message = render_error(&e);
+
+                Err(match e {
+                    Error::Inner(e) | Error::Events(e) => match e {},
+                    Error::DuplicatePlayer(_) => DuplicatePlayer::new_err(message),
+                    Error::PlayerNotFound(_) => PlayerNotFound::new_err(message),
+                    Error::InvalidRoll(..) => InvalidRoll::new_err(message),
+                    Error::NotYourTurn(_) => NotYourTurn::new_err(message),
+                    Error::InvalidPosition(..) => InvalidPosition::new_err(message),
+                })
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl Game {
+    #[new]
+    #[pyo3(signature = (seed = 0))]
+    fn new(seed: u64) -> Self {
+        Game {
+            the_goose: SampleTheGoose::with_die(SampleDie::new(1..=6, seed)),
+        }
+    }
+
+    fn add(&mut self, player: String) -> PyResult<Vec<Event>> {
+        self.execute(Command::Add(player))
+    }
+
+    fn remove(&mut self, player: String) -> PyResult<Vec<Event>> {
+        self.execute(Command::Remove(player))
+    }
+
+    fn rename(&mut self, player: String, name: String) -> PyResult<Vec<Event>> {
+        self.execute(Command::Rename(player, name))
+    }
+
+    #[pyo3(name = "move")]
+    fn move_player(&mut self, player: String, first: u32, second: u32) -> PyResult<Vec<Event>> {
+        self.execute(Command::Move(player, [first, second]))
+    }
+
+    fn roll(&mut self, player: String) -> PyResult<Vec<Event>> {
+        self.execute(Command::RollAndMove(player))
+    }
+
+    fn players(&self) -> Vec<String> {
+        self.the_goose.keys().cloned().collect()
+    }
+
+    fn positions(&self) -> Vec<(String, u32)> {
+        self.the_goose
+            .iter()
+            .map(|(player, position)| (player.clone(), (*position).into()))
+            .collect()
+    }
+}
+
+struct Winner(bool);
+
+impl Events<u32, SamplePosition, [u32; 2]> for Winner {
+    type Error = Infallible;
+
+    fn notify(
+        &mut self,
+        event: the_goose::Event<u32, SamplePosition, [u32; 2]>,
+    ) -> Result<(), Self::Error> {
+        self.0 |= matches!(event, the_goose::Event::Win(_));
+
+        Ok(())
+    }
+}
+
+fn play(players: u32, seed: u64, max_rounds: u32) -> Option<(u32, u32)> {
+    let mut the_goose = SampleTheGoose::with_die(SampleDie::new(1..=6, seed));
+    let mut winner = Winner(false);
+
+    for player in 0..players {
+        the_goose.execute(Command::Add(player), &mut winner).ok();
+    }
+
+    for round in 1..=max_rounds {
+        for player in 0..players {
+            the_goose
+                .execute(Command::RollAndMove(player), &mut winner)
+                .ok();
+            if winner.0 {
+                return Some((player, round));
+            }
+        }
+    }
+
+    None
+}
+
+#[pyfunction]
+#[pyo3(signature = (players, games, seed = 0, max_rounds = 1000))]
+fn simulate(
+    py: Python<'_>,
+    players: u32,
+    games: u32,
+    seed: u64,
+    max_rounds: u32,
+) -> Vec<Option<(u32, u32)>> {
+    py.detach(|| {
+        (0..u64::from(games))
+            .map(|game| play(players, seed.wrapping_add(game), max_rounds))
+            .collect()
+    })
+}
+
+#[pymodule]
+#[pyo3(name = "the_goose")]
+fn python(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Game>()?;
+    m.add_class::<Event>()?;
+    m.add_function(wrap_pyfunction!(simulate, m)?)?;
+    m.add("DuplicatePlayer", m.py().get_type::<DuplicatePlayer>())?;
+    m.add("PlayerNotFound", m.py().get_type::<PlayerNotFound>())?;
+    m.add("InvalidRoll", m.py().get_type::<InvalidRoll>())?;
+    m.add("NotYourTurn", m.py().get_type::<NotYourTurn>())?;
+    m.add("InvalidPosition", m.py().get_type::<InvalidPosition>())?;
+
+    Ok(())
+}
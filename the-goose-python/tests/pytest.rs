@@ -0,0 +1,54 @@
+use std::path::Path;
+use std::process::Command;
+
+const FALLBACK: &str = "import sys, test_the_goose as t
+for name in dir(t):
+    if name.startswith('test_'):
+        getattr(t, name)()
+        print(name, 'ok')";
+
+fn python() -> Command {
+    Command::new(std::env::var("PYTHON").unwrap_or_else(|_| "python3".to_string()))
+}
+
+#[test]
+fn test_python() {
+    let tests = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/python");
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("the_goose_python");
+    let target_dir = std::env::current_exe()
+        .unwrap()
+        .parent()
+        .and_then(Path::parent)
+        .unwrap()
+        .to_path_buf();
+
+    std::fs::create_dir_all(&out_dir).unwrap();
+    std::fs::copy(
+        target_dir.join("libthe_goose_python.so"),
+        out_dir.join("the_goose.so"),
+    )
+    .unwrap();
+
+    let pytest = python()
+        .args(["-c", "import pytest"])
+        .output()
+        .is_ok_and(|output| output.status.success());
+
+    let mut command = python();
+    if pytest {
+        command
+            .args(["-m", "pytest", "-q", "-p", "no:cacheprovider"])
+            .arg(&tests);
+    } else {
+        command.args(["-c", FALLBACK]);
+    }
+
+    let path = std::env::join_paths([&out_dir, &tests]).unwrap();
+    let output = command.env("PYTHONPATH", path).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
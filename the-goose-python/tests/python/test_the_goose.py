@@ -0,0 +1,90 @@
+import the_goose
+
+
+def raises(exception, call, *args):
+    try:
+        call(*args)
+    except exception as e:
+        return str(e)
+
+    raise AssertionError(f"{exception.__name__} not raised")
+
+
+def test_add():
+    game = the_goose.Game()
+
+    [event] = game.add("Pippo")
+    assert (event.kind, event.player) == ("PlayerJoined", "Pippo")
+
+    message = raises(the_goose.DuplicatePlayer, game.add, "Pippo")
+    assert message == "Pippo: already existing player"
+    assert game.players() == ["Pippo"]
+
+
+def test_move():
+    game = the_goose.Game()
+    game.add("Pippo")
+
+    roll, moved, moved_again = game.move("Pippo", 2, 3)
+    assert (roll.kind, roll.player, roll.roll) == ("Roll", "Pippo", (2, 3))
+    assert (moved.kind, moved.start, moved.end) == ("Moved", 0, 5)
+    assert (moved_again.kind, moved_again.start, moved_again.end) == ("MovedAgain", 5, 10)
+    assert (moved.square, moved.cause, moved.sequence) == ("TheGoose", "Roll", 0)
+    assert (moved_again.square, moved_again.cause, moved_again.sequence) == ("Normal", "TheGoose", 1)
+    assert roll.square is None
+    assert game.positions() == [("Pippo", 10)]
+
+
+def test_errors():
+    game = the_goose.Game()
+    game.add("Pippo")
+
+    assert raises(the_goose.InvalidRoll, game.move, "Pippo", 2, 7) == "Pippo: invalid roll 7"
+    assert raises(the_goose.PlayerNotFound, game.roll, "Pluto") == "Pluto: unknown player"
+    assert issubclass(the_goose.InvalidRoll, ValueError)
+    assert game.positions() == [("Pippo", 0)]
+
+
+def test_remove():
+    game = the_goose.Game()
+    game.add("Pippo")
+    game.add("Pluto")
+
+    [event] = game.remove("Pippo")
+    assert (event.kind, event.player) == ("PlayerLeft", "Pippo")
+    assert game.players() == ["Pluto"]
+
+
+def test_rename():
+    game = the_goose.Game()
+    game.add("Pippo")
+    game.add("Pluto")
+    game.move("Pippo", 1, 2)
+
+    [event] = game.rename("Pippo", "Paperino")
+    assert (event.kind, event.previous, event.player) == ("PlayerRenamed", "Pippo", "Paperino")
+    assert game.positions() == [("Paperino", 3), ("Pluto", 0)]
+
+    message = raises(the_goose.DuplicatePlayer, game.rename, "Paperino", "Pluto")
+    assert message == "Pluto: already existing player"
+    assert raises(the_goose.PlayerNotFound, game.rename, "Pippo", "Topolino") == "Pippo: unknown player"
+
+
+def test_seeded_rolls():
+    def play(seed):
+        game = the_goose.Game(seed)
+        game.add("Pippo")
+
+        return [event.roll for _ in range(10) for event in game.roll("Pippo") if event.roll]
+
+    assert play(7) == play(7)
+    assert play(7) != play(8)
+
+
+def test_simulate():
+    results = the_goose.simulate(3, 50, seed=1)
+
+    assert len(results) == 50
+    assert all(0 <= winner < 3 and round >= 1 for winner, round in results)
+    assert results == the_goose.simulate(3, 50, seed=1)
+    assert the_goose.simulate(2, 3, max_rounds=1) == [None, None, None]
@@ -0,0 +1,801 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use axum::extract::ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post, put};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use sample::locale::Locale;
+use sample::{collect, SampleDie, SamplePosition, SampleTheGoose};
+use the_goose::{Command, Error, Event};
+
+pub type GameEvent = Event<String, SamplePosition, [u32; 2]>;
+
+const BACKLOG: usize = 64;
+
+struct Game {
+    the_goose: SampleTheGoose<String, SamplePosition>,
+    events: broadcast::Sender<GameEvent>,
+    locale: Locale,
+}
+
+#[derive(Default)]
+struct Games {
+    next: u64,
+    games: HashMap<u64, Game>,
+}
+
+#[derive(Clone, Default)]
+pub struct Server(Arc<Mutex<Games>>);
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct Created {
+    pub id: u64,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct NewPlayer {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct Dice {
+    pub roll: [u32; 2],
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct NewPosition {
+    pub position: u32,
+}
+
+#[derive(Serialize, Deserialize, Default, PartialEq, Debug)]
+pub struct Settings {
+    pub locale: Option<Locale>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct Failure {
+    pub error: String,
+}
+
+impl Server {
+    pub fn new() -> Self {
+        Server::default()
+    }
+
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/games", post(create))
+            .route("/games/{id}", get(positions))
+            .route("/games/{id}/players", get(players).post(add))
+            .route("/games/{id}/players/{name}", delete(remove).put(rename))
+            .route("/games/{id}/players/{name}/move", post(move_player))
+            .route("/games/{id}/players/{name}/roll", post(roll_and_move))
+            .route("/games/{id}/players/{name}/position", put(set_position))
+            .route("/games/{id}/players/{name}/kick", post(kick))
+            .route("/games/{id}/reset", post(reset))
+            .route("/games/{id}/rematch", post(rematch))
+            .route("/games/{id}/events", get(events))
+            .route("/games/{id}/messages", get(messages))
+            .with_state(self)
+    }
+
+    fn create(&self, locale: Locale) -> u64 {
+        let mut games = self.0.lock().unwrap();
+
+        games.next += 1;
+        let id = games.next;
+        games.games.insert(
+            id,
+            Game {
+                the_goose: SampleTheGoose::with_die(SampleDie::new(1..=6, id)),
+                events: broadcast::channel(BACKLOG).0,
+                locale,
+            },
+        );
+
+        id
+    }
+
+    fn execute(&self, id: u64, command: Command<String, [u32; 2]>, settings: Settings) -> Response {
+        let mut games = self.0.lock().unwrap();
+        let Some(game) = games.games.get_mut(&id) else {
+            return unknown_game(id);
+        };
+
+        match collect(&mut game.the_goose, command) {
+            Ok(events) => {
+                for event in events.iter() {
+                    game.events.send(event.clone()).ok();
+                }
+
+                Json(events.to_vec()).into_response()
+            }
+            Err(e) => {
+                let status = match &e {
+                    Error::Inner(e) | Error::Events(e) => match *e {},
+                    Error::DuplicatePlayer(_) => StatusCode::CONFLICT,
+                    Error::PlayerNotFound(_) => StatusCode::NOT_FOUND,
+                    Error::InvalidRoll(..) => StatusCode::UNPROCESSABLE_ENTITY,
+                    Error::NotYourTurn(_) => StatusCode::CONFLICT,
+                    Error::InvalidPosition(..) => StatusCode::UNPROCESSABLE_ENTITY,
+                };
+
+                failure(
+                    status,
+                    settings.locale.unwrap_or(game.locale).render_error(&e),
+                )
+            }
+        }
+    }
+}
+
+pub async fn serve(listener: TcpListener) -> std::io::Result<()> {
+    axum::serve(listener, Server::new().router()).await
+}
+
+fn failure(status: StatusCode, error: String) -> Response {
+    (status, Json(Failure { error })).into_response()
+}
+
+fn unknown_game(id: u64) -> Response {
+    failure(StatusCode::NOT_FOUND, format!("{id}: unknown game"))
+}
+
+async fn create(State(server): State<Server>, Query(settings): Query<Settings>) -> Response {
+    (
+        StatusCode::CREATED,
+        Json(Created {
+            id: server.create(settings.locale.unwrap_or_default()),
+        }),
+    )
+        .into_response()
+}
+
+async fn positions(State(server): State<Server>, Path(id): Path<u64>) -> Response {
+    match server.0.lock().unwrap().games.get(&id) {
+        Some(game) => Json(
+            game.the_goose
+                .iter()
+                .map(|(player, position)| (player.clone(), *position))
+                .collect::<BTreeMap<_, _>>(),
+        )
+        .into_response(),
+        None => unknown_game(id),
+    }
+}
+
+async fn players(State(server): State<Server>, Path(id): Path<u64>) -> Response {
+    match server.0.lock().unwrap().games.get(&id) {
+        Some(game) => Json(game.the_goose.keys().cloned().collect::<Vec<_>>()).into_response(),
+        None => unknown_game(id),
+    }
+}
+
+async fn add(
+    State(server): State<Server>,
+    Path(id): Path<u64>,
+    Query(settings): Query<Settings>,
+    Json(player): Json<NewPlayer>,
+) -> Response {
+    server.execute(id, Command::Add(player.name), settings)
+}
+
+async fn remove(
+    State(server): State<Server>,
+    Path((id, name)): Path<(u64, String)>,
+    Query(settings): Query<Settings>,
+) -> Response {
+    server.execute(id, Command::Remove(name), settings)
+}
+
+async fn rename(
+    State(server): State<Server>,
+    Path((id, name)): Path<(u64, String)>,
+    Query(settings): Query<Settings>,
+    Json(player): Json<NewPlayer>,
+) -> Response {
+    server.execute(id, Command::Rename(name, player.name), settings)
+}
+
+async fn move_player(
+    State(server): State<Server>,
+    Path((id, name)): Path<(u64, String)>,
+    Query(settings): Query<Settings>,
+    Json(dice): Json<Dice>,
+) -> Response {
+    server.execute(id, Command::Move(name, dice.roll), settings)
+}
+
+async fn roll_and_move(
+    State(server): State<Server>,
+    Path((id, name)): Path<(u64, String)>,
+    Query(settings): Query<Settings>,
+) -> Response {
+    server.execute(id, Command::RollAndMove(name), settings)
+}
+
+async fn set_position(
+    State(server): State<Server>,
+    Path((id, name)): Path<(u64, String)>,
+    Query(settings): Query<Settings>,
+    Json(position): Json<NewPosition>,
+) -> Response {
+    server.execute(id, Command::SetPosition(name, position.position), settings)
+}
+
+async fn kick(
+    State(server): State<Server>,
+    Path((id, name)): Path<(u64, String)>,
+    Query(settings): Query<Settings>,
+) -> Response {
+    server.execute(id, Command::Kick(name), settings)
+}
+
+async fn reset(
+    State(server): State<Server>,
+    Path(id): Path<u64>,
+    Query(settings): Query<Settings>,
+) -> Response {
+    server.execute(id, Command::Reset, settings)
+}
+
+async fn rematch(
+    State(server): State<Server>,
+    Path(id): Path<u64>,
+    Query(settings): Query<Settings>,
+) -> Response {
+    server.execute(id, Command::Rematch, settings)
+}
+
+async fn events(
+    State(server): State<Server>,
+    Path(id): Path<u64>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let receiver = match server.0.lock().unwrap().games.get(&id) {
+        Some(game) => game.events.subscribe(),
+        None => return unknown_game(id),
+    };
+
+    ws.on_upgrade(move |socket| {
+        stream(socket, receiver, |event| {
+            serde_json::to_string(event).unwrap()
+        })
+    })
+}
+
+async fn messages(
+    State(server): State<Server>,
+    Path(id): Path<u64>,
+    Query(settings): Query<Settings>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let (receiver, locale) = match server.0.lock().unwrap().games.get(&id) {
+        Some(game) => (
+            game.events.subscribe(),
+            settings.locale.unwrap_or(game.locale),
+        ),
+        None => return unknown_game(id),
+    };
+
+    ws.on_upgrade(move |socket| {
+        stream(socket, receiver, move |event| {
+            locale.render(core::slice::from_ref(event))
+        })
+    })
+}
+
+async fn stream(
+    mut socket: WebSocket,
+    mut receiver: broadcast::Receiver<GameEvent>,
+    text: impl Fn(&GameEvent) -> String,
+) {
+    loop {
+        let message = match receiver.recv().await {
+            Ok(event) => Message::Text(text(&event).into()),
+            // Events were dropped: close so the client reconnects and resyncs.
+            Err(RecvError::Lagged(_)) => Message::Close(Some(CloseFrame {
+                code: close_code::AGAIN,
+                reason: "lagged".into(),
+            })),
+            Err(RecvError::Closed) => break,
+        };
+        let close = matches!(message, Message::Close(_));
+
+        if socket.send(message).await.is_err() || close {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_util::StreamExt;
+    use std::net::SocketAddr;
+    use the_goose::{Cause, Landing, PositionType};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+    use tokio_tungstenite::tungstenite;
+
+    async fn start() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(serve(listener));
+
+        address
+    }
+
+    async fn request(
+        address: SocketAddr,
+        method: &str,
+        path: &str,
+        body: Option<&str>,
+    ) -> (u16, serde_json::Value) {
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        let body = body.unwrap_or_default();
+
+        stream
+            .write_all(
+                format!(
+                    "{method} {path} HTTP/1.1\r\nHost: {address}\r\nConnection: close\r\n\
+                     Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+                    body.len()
+                )
+                .as_bytes(),
+            )
+            .await
+            .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+
+        (
+            status,
+            serde_json::from_str(body).unwrap_or_else(|_| body.into()),
+        )
+    }
+
+    fn json<T: Serialize>(value: T) -> serde_json::Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    fn name(name: &str) -> String {
+        name.to_string()
+    }
+
+    fn landing(square: PositionType, cause: Cause, sequence: u32) -> Landing {
+        Landing {
+            square,
+            cause,
+            sequence,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rest() {
+        let address = start().await;
+
+        assert_eq!(
+            request(address, "POST", "/games", None).await,
+            (201, json(Created { id: 1 }))
+        );
+
+        assert_eq!(
+            request(
+                address,
+                "POST",
+                "/games/1/players",
+                Some(r#"{"name":"Pippo"}"#)
+            )
+            .await,
+            (200, json([GameEvent::PlayerJoined(name("Pippo"))]))
+        );
+        assert_eq!(
+            request(
+                address,
+                "POST",
+                "/games/1/players",
+                Some(r#"{"name":"Pippo"}"#)
+            )
+            .await,
+            (
+                409,
+                json(Failure {
+                    error: name("Pippo: already existing player")
+                })
+            )
+        );
+
+        assert_eq!(
+            request(
+                address,
+                "POST",
+                "/games/1/players/Pippo/move",
+                Some(r#"{"roll":[2,3]}"#)
+            )
+            .await,
+            (
+                200,
+                json([
+                    GameEvent::Roll(name("Pippo"), [2, 3]),
+                    GameEvent::Moved(
+                        name("Pippo"),
+                        0.into(),
+                        5.into(),
+                        landing(PositionType::TheGoose, Cause::Roll, 0)
+                    ),
+                    GameEvent::MovedAgain(
+                        name("Pippo"),
+                        5.into(),
+                        10.into(),
+                        landing(PositionType::Normal, Cause::TheGoose, 1)
+                    ),
+                ])
+            )
+        );
+        assert_eq!(
+            request(
+                address,
+                "POST",
+                "/games/1/players/Pippo/move",
+                Some(r#"{"roll":[2,7]}"#)
+            )
+            .await,
+            (
+                422,
+                json(Failure {
+                    error: name("Pippo: invalid roll 7")
+                })
+            )
+        );
+        assert_eq!(
+            request(address, "POST", "/games/1/players/Pluto/roll", None)
+                .await
+                .0,
+            404
+        );
+
+        let (status, events) = request(address, "POST", "/games/1/players/Pippo/roll", None).await;
+        assert_eq!(status, 200);
+        assert!(events[0]["Roll"][0] == "Pippo");
+
+        assert_eq!(
+            request(address, "DELETE", "/games/1/players/Pippo", None).await,
+            (200, json([GameEvent::PlayerLeft(name("Pippo"))]))
+        );
+        assert_eq!(
+            request(address, "DELETE", "/games/1/players/Pippo", None).await,
+            (
+                404,
+                json(Failure {
+                    error: name("Pippo: unknown player")
+                })
+            )
+        );
+        assert_eq!(
+            request(address, "GET", "/games/1", None).await,
+            (200, serde_json::json!({}))
+        );
+
+        assert_eq!(
+            request(address, "GET", "/games/2", None).await,
+            (
+                404,
+                json(Failure {
+                    error: name("2: unknown game")
+                })
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_players() {
+        let address = start().await;
+
+        request(address, "POST", "/games", None).await;
+        for player in ["Pippo", "Pluto"] {
+            request(
+                address,
+                "POST",
+                "/games/1/players",
+                Some(&format!(r#"{{"name":"{player}"}}"#)),
+            )
+            .await;
+        }
+
+        assert_eq!(
+            request(
+                address,
+                "PUT",
+                "/games/1/players/Pippo",
+                Some(r#"{"name":"Paperino"}"#)
+            )
+            .await,
+            (
+                200,
+                json([GameEvent::PlayerRenamed(name("Pippo"), name("Paperino"))])
+            )
+        );
+        assert_eq!(
+            request(
+                address,
+                "PUT",
+                "/games/1/players/Paperino",
+                Some(r#"{"name":"Pluto"}"#)
+            )
+            .await
+            .0,
+            409
+        );
+        assert_eq!(
+            request(address, "GET", "/games/1/players", None).await,
+            (200, json(["Paperino", "Pluto"]))
+        );
+        assert_eq!(
+            request(address, "GET", "/games/2/players", None).await.0,
+            404
+        );
+    }
+
+    #[tokio::test]
+    async fn test_admin() {
+        let address = start().await;
+
+        request(address, "POST", "/games", None).await;
+        for player in ["Pippo", "Pluto"] {
+            request(
+                address,
+                "POST",
+                "/games/1/players",
+                Some(&format!(r#"{{"name":"{player}"}}"#)),
+            )
+            .await;
+        }
+
+        assert_eq!(
+            request(
+                address,
+                "PUT",
+                "/games/1/players/Pippo/position",
+                Some(r#"{"position":60}"#)
+            )
+            .await,
+            (
+                200,
+                json([GameEvent::PositionSet(name("Pippo"), 60.into())])
+            )
+        );
+        assert_eq!(
+            request(
+                address,
+                "PUT",
+                "/games/1/players/Pippo/position",
+                Some(r#"{"position":64}"#)
+            )
+            .await
+            .0,
+            422
+        );
+        assert_eq!(
+            request(address, "POST", "/games/1/reset", None).await,
+            (200, json([GameEvent::Reset]))
+        );
+        assert_eq!(
+            request(address, "POST", "/games/1/players/Pluto/kick", None).await,
+            (200, json([GameEvent::Kicked(name("Pluto"))]))
+        );
+        assert_eq!(
+            request(address, "POST", "/games/1/players/Pluto/kick", None)
+                .await
+                .0,
+            404
+        );
+        assert_eq!(
+            request(address, "POST", "/games/1/rematch", None).await,
+            (200, json([GameEvent::Rematch]))
+        );
+        assert_eq!(
+            request(address, "GET", "/games/1", None).await,
+            (200, serde_json::json!({"Pippo": 0}))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_events() {
+        let address = start().await;
+
+        request(address, "POST", "/games", None).await;
+        request(address, "POST", "/games", None).await;
+
+        let (mut socket, _) =
+            tokio_tungstenite::connect_async(format!("ws://{address}/games/1/events"))
+                .await
+                .unwrap();
+
+        request(
+            address,
+            "POST",
+            "/games/2/players",
+            Some(r#"{"name":"Pluto"}"#),
+        )
+        .await;
+        request(
+            address,
+            "POST",
+            "/games/1/players",
+            Some(r#"{"name":"Pippo"}"#),
+        )
+        .await;
+        request(
+            address,
+            "POST",
+            "/games/1/players",
+            Some(r#"{"name":"Pippo"}"#),
+        )
+        .await;
+        request(
+            address,
+            "POST",
+            "/games/1/players/Pippo/move",
+            Some(r#"{"roll":[1,1]}"#),
+        )
+        .await;
+
+        let mut events = Vec::new();
+        while events.len() < 3 {
+            match socket.next().await.unwrap().unwrap() {
+                tungstenite::Message::Text(text) => {
+                    events.push(serde_json::from_str::<GameEvent>(&text).unwrap())
+                }
+                _ => continue,
+            }
+        }
+
+        assert_eq!(
+            events,
+            vec![
+                GameEvent::PlayerJoined(name("Pippo")),
+                GameEvent::Roll(name("Pippo"), [1, 1]),
+                GameEvent::Moved(
+                    name("Pippo"),
+                    0.into(),
+                    2.into(),
+                    landing(PositionType::Normal, Cause::Roll, 0)
+                ),
+            ]
+        );
+
+        assert!(
+            tokio_tungstenite::connect_async(format!("ws://{address}/games/3/events"))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lagged() {
+        let server = Server::new();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let router = server.clone().router();
+
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        request(address, "POST", "/games", None).await;
+        let (mut socket, _) =
+            tokio_tungstenite::connect_async(format!("ws://{address}/games/1/events"))
+                .await
+                .unwrap();
+
+        for _ in 0..=BACKLOG {
+            server.0.lock().unwrap().games[&1]
+                .events
+                .send(GameEvent::Reset)
+                .unwrap();
+        }
+
+        match socket.next().await.unwrap().unwrap() {
+            tungstenite::Message::Close(Some(frame)) => {
+                assert_eq!(u16::from(frame.code), close_code::AGAIN);
+                assert_eq!(frame.reason.as_str(), "lagged");
+            }
+            message => panic!("unexpected {message:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_locale() {
+        let address = start().await;
+
+        request(address, "POST", "/games?locale=it", None).await;
+        let (mut socket, _) =
+            tokio_tungstenite::connect_async(format!("ws://{address}/games/1/messages?locale=es"))
+                .await
+                .unwrap();
+
+        request(
+            address,
+            "POST",
+            "/games/1/players",
+            Some(r#"{"name":"Pippo"}"#),
+        )
+        .await;
+        assert_eq!(
+            request(
+                address,
+                "POST",
+                "/games/1/players",
+                Some(r#"{"name":"Pippo"}"#)
+            )
+            .await,
+            (
+                409,
+                json(Failure {
+                    error: name("Pippo: giocatore già esistente")
+                })
+            )
+        );
+        assert_eq!(
+            request(
+                address,
+                "POST",
+                "/games/1/players/Pluto/roll?locale=fr",
+                None
+            )
+            .await,
+            (
+                404,
+                json(Failure {
+                    error: name("Pluto : joueur inconnu")
+                })
+            )
+        );
+        assert_eq!(
+            request(
+                address,
+                "POST",
+                "/games/1/players/Pluto/roll?locale=de",
+                None
+            )
+            .await
+            .0,
+            400
+        );
+        request(
+            address,
+            "POST",
+            "/games/1/players/Pippo/move",
+            Some(r#"{"roll":[2,4]}"#),
+        )
+        .await;
+
+        let mut messages = Vec::new();
+        while messages.len() < 4 {
+            if let tungstenite::Message::Text(text) = socket.next().await.unwrap().unwrap() {
+                messages.push(text.to_string());
+            }
+        }
+
+        assert_eq!(
+            messages,
+            vec![
+                name("Pippo entra en el juego"),
+                name("Pippo tira 2, 4"),
+                name("Pippo se mueve de la Salida al Puente"),
+                name("Pippo salta a 12"),
+            ]
+        );
+    }
+}
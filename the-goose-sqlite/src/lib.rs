@@ -0,0 +1,528 @@
+use std::marker::PhantomData;
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use sample::SampleDie;
+use the_goose::{ExtraTurns, GooseRule, Position, State, TheGoose, Turn};
+
+const SCHEMA: &str = "
+    PRAGMA foreign_keys = ON;
+
+    CREATE TABLE IF NOT EXISTS games (
+        id INTEGER PRIMARY KEY,
+        status TEXT NOT NULL DEFAULT 'playing' CHECK (status IN ('playing', 'won')),
+        winner TEXT
+    );
+
+    CREATE TABLE IF NOT EXISTS players (
+        game_id INTEGER NOT NULL REFERENCES games (id) ON DELETE CASCADE,
+        name TEXT NOT NULL,
+        position INTEGER NOT NULL DEFAULT 0,
+        PRIMARY KEY (game_id, name)
+    );
+
+    CREATE INDEX IF NOT EXISTS players_by_position ON players (game_id, position, name);
+
+    CREATE TABLE IF NOT EXISTS turns (
+        game_id INTEGER PRIMARY KEY REFERENCES games (id) ON DELETE CASCADE,
+        player TEXT,
+        extra_turns INTEGER NOT NULL DEFAULT 0
+    );
+";
+
+#[derive(PartialEq, Debug)]
+pub enum Status {
+    Playing,
+    Won(String),
+}
+
+pub struct SqliteState<P> {
+    connection: Connection,
+    game: i64,
+    positions: PhantomData<P>,
+}
+
+impl<P> SqliteState<P> {
+    pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Self> {
+        SqliteState::new(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        SqliteState::new(Connection::open_in_memory()?)
+    }
+
+    pub fn new(connection: Connection) -> rusqlite::Result<Self> {
+        connection.execute_batch(SCHEMA)?;
+        connection.execute("INSERT INTO games DEFAULT VALUES", [])?;
+
+        let game = connection.last_insert_rowid();
+
+        Ok(SqliteState {
+            connection,
+            game,
+            positions: PhantomData,
+        })
+    }
+
+    pub fn resume(connection: Connection, game: i64) -> rusqlite::Result<Self> {
+        connection.execute_batch(SCHEMA)?;
+        connection.query_row("SELECT id FROM games WHERE id = ?1", [game], |_| Ok(()))?;
+
+        Ok(SqliteState {
+            connection,
+            game,
+            positions: PhantomData,
+        })
+    }
+
+    pub fn game(&self) -> i64 {
+        self.game
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    pub fn status(&self) -> rusqlite::Result<Status> {
+        self.connection.query_row(
+            "SELECT status, winner FROM games WHERE id = ?1",
+            [self.game],
+            |row| {
+                Ok(match row.get::<_, Option<String>>(1)? {
+                    Some(winner) => Status::Won(winner),
+                    None => Status::Playing,
+                })
+            },
+        )
+    }
+
+    fn names(&self, sql: &str, params: impl rusqlite::Params) -> rusqlite::Result<Vec<String>> {
+        self.connection
+            .prepare_cached(sql)?
+            .query_map(params, |row| row.get(0))?
+            .collect()
+    }
+}
+
+impl<P> State<String, P, Vec<String>> for SqliteState<P>
+where
+    P: Position<u32> + Into<u32> + Copy,
+{
+    type Error = rusqlite::Error;
+
+    fn get_player_position(&self, player: &String) -> Result<Option<P>, Self::Error> {
+        Ok(self
+            .connection
+            .prepare_cached("SELECT position FROM players WHERE game_id = ?1 AND name = ?2")?
+            .query_row(params![self.game, player], |row| row.get::<_, u32>(0))
+            .optional()?
+            .map(P::from))
+    }
+
+    fn add_player(&mut self, player: String) -> Result<(), Self::Error> {
+        self.connection
+            .prepare_cached("INSERT INTO players (game_id, name) VALUES (?1, ?2)")?
+            .execute(params![self.game, player])?;
+
+        Ok(())
+    }
+
+    fn remove_player(&mut self, player: &String) -> Result<(), Self::Error> {
+        self.connection
+            .prepare_cached("DELETE FROM players WHERE game_id = ?1 AND name = ?2")?
+            .execute(params![self.game, player])?;
+
+        Ok(())
+    }
+
+    fn find_players_by_position(&self, position: &P) -> Result<Vec<String>, Self::Error> {
+        self.names(
+            "SELECT name FROM players WHERE game_id = ?1 AND position = ?2 ORDER BY name",
+            params![self.game, (*position).into()],
+        )
+    }
+
+    fn players(&self) -> Result<Vec<String>, Self::Error> {
+        self.names(
+            "SELECT name FROM players WHERE game_id = ?1 ORDER BY name",
+            params![self.game],
+        )
+    }
+
+    fn update_player_position(&mut self, player: &String, position: &P) -> Result<(), Self::Error> {
+        self.connection
+            .prepare_cached("UPDATE players SET position = ?3 WHERE game_id = ?1 AND name = ?2")?
+            .execute(params![self.game, player, (*position).into()])?;
+
+        Ok(())
+    }
+
+    fn begin(&mut self) -> Result<(), Self::Error> {
+        self.connection.execute_batch("BEGIN IMMEDIATE")
+    }
+
+    fn commit(&mut self) -> Result<(), Self::Error> {
+        self.connection.execute_batch("COMMIT")
+    }
+
+    fn rollback(&mut self) -> Result<(), Self::Error> {
+        self.connection.execute_batch("ROLLBACK")
+    }
+
+    fn turn(&self) -> Result<Turn<String>, Self::Error> {
+        Ok(self
+            .connection
+            .prepare_cached("SELECT player, extra_turns FROM turns WHERE game_id = ?1")?
+            .query_row([self.game], |row| {
+                Ok(Turn {
+                    player: row.get(0)?,
+                    extra_turns: row.get(1)?,
+                })
+            })
+            .optional()?
+            .unwrap_or_default())
+    }
+
+    fn update_turn(&mut self, turn: Turn<String>) -> Result<(), Self::Error> {
+        self.connection
+            .prepare_cached(
+                "INSERT INTO turns (game_id, player, extra_turns) VALUES (?1, ?2, ?3)
+                 ON CONFLICT (game_id) DO UPDATE SET player = ?2, extra_turns = ?3",
+            )?
+            .execute(params![self.game, turn.player, turn.extra_turns])?;
+
+        Ok(())
+    }
+
+    fn update_winner(&mut self, winner: Option<&String>) -> Result<(), Self::Error> {
+        let status = if winner.is_some() { "won" } else { "playing" };
+
+        self.connection
+            .prepare_cached("UPDATE games SET status = ?2, winner = ?3 WHERE id = ?1")?
+            .execute(params![self.game, status, winner])?;
+
+        Ok(())
+    }
+}
+
+pub struct SqliteTheGoose<P> {
+    state: SqliteState<P>,
+    die: SampleDie,
+    goose_rule: GooseRule,
+    extra_turns: ExtraTurns,
+}
+
+impl<P> SqliteTheGoose<P> {
+    pub fn new(state: SqliteState<P>, die: SampleDie) -> Self {
+        SqliteTheGoose {
+            state,
+            die,
+            goose_rule: GooseRule::default(),
+            extra_turns: ExtraTurns::default(),
+        }
+    }
+
+    pub fn with_goose_rule(mut self, goose_rule: GooseRule) -> Self {
+        self.goose_rule = goose_rule;
+        self
+    }
+
+    pub fn with_extra_turns(mut self, extra_turns: ExtraTurns) -> Self {
+        self.extra_turns = extra_turns;
+        self
+    }
+
+    pub fn into_state(self) -> SqliteState<P> {
+        self.state
+    }
+}
+
+impl<P> TheGoose<String, P, [u32; 2], Vec<String>> for SqliteTheGoose<P>
+where
+    P: Position<u32> + Into<u32> + Copy,
+{
+    type State = SqliteState<P>;
+
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+
+    fn roll_dice(&mut self) -> [u32; 2] {
+        [self.die.roll(), self.die.roll()]
+    }
+
+    fn is_valid_face(&self, face: &u32) -> bool {
+        self.die.contains(face)
+    }
+
+    fn goose_rule(&self) -> GooseRule {
+        self.goose_rule
+    }
+
+    fn extra_turns(&self) -> ExtraTurns {
+        self.extra_turns
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use sample::collect;
+    use sample::SamplePosition;
+    use the_goose::{Cause, Command, Error, Event, Landing, PositionType};
+
+    fn the_goose() -> SqliteTheGoose<SamplePosition> {
+        SqliteTheGoose::new(SqliteState::open_in_memory().unwrap(), SampleDie::default())
+    }
+
+    fn name(name: &str) -> String {
+        name.to_string()
+    }
+
+    #[test]
+    fn test_players() {
+        let mut the_goose = the_goose();
+
+        assert_eq!(
+            collect(&mut the_goose, Command::Add(name("Pippo"))),
+            Ok(vec![Event::PlayerJoined(name("Pippo"))].into())
+        );
+        assert_eq!(
+            collect(&mut the_goose, Command::Add(name("Pluto"))),
+            Ok(vec![Event::PlayerJoined(name("Pluto"))].into())
+        );
+        assert_eq!(
+            collect(&mut the_goose, Command::Add(name("Pippo"))),
+            Err(Error::DuplicatePlayer(name("Pippo")))
+        );
+        assert_eq!(
+            collect(&mut the_goose, Command::Remove(name("Pippo"))),
+            Ok(vec![Event::PlayerLeft(name("Pippo"))].into())
+        );
+    }
+
+    #[test]
+    fn test_move_and_prank() {
+        let mut the_goose = the_goose();
+
+        collect(&mut the_goose, Command::Add(name("Pippo"))).ok();
+        collect(&mut the_goose, Command::Add(name("Pluto"))).ok();
+        the_goose
+            .state_mut()
+            .update_player_position(&name("Pippo"), &SamplePosition::from(15))
+            .unwrap();
+        the_goose
+            .state_mut()
+            .update_player_position(&name("Pluto"), &SamplePosition::from(17))
+            .unwrap();
+
+        assert_eq!(
+            collect(&mut the_goose, Command::Move(name("Pippo"), [1, 1])),
+            Ok(vec![
+                Event::Roll(name("Pippo"), [1, 1]),
+                Event::Moved(
+                    name("Pippo"),
+                    15.into(),
+                    17.into(),
+                    Landing {
+                        square: PositionType::Normal,
+                        cause: Cause::Roll,
+                        sequence: 0,
+                    }
+                ),
+                Event::Prank(name("Pluto"), 17.into(), 15.into())
+            ]
+            .into())
+        );
+
+        assert_eq!(
+            the_goose.state().find_players_by_position(&15.into()),
+            Ok(vec![name("Pluto")])
+        );
+    }
+
+    #[test]
+    fn test_win_status() {
+        let mut the_goose = the_goose();
+
+        collect(&mut the_goose, Command::Add(name("Pippo"))).ok();
+        the_goose
+            .state_mut()
+            .update_player_position(&name("Pippo"), &SamplePosition::from(60))
+            .unwrap();
+
+        assert_eq!(the_goose.state().status(), Ok(Status::Playing));
+
+        collect(&mut the_goose, Command::Move(name("Pippo"), [1, 2])).unwrap();
+
+        assert_eq!(the_goose.state().status(), Ok(Status::Won(name("Pippo"))));
+
+        collect(&mut the_goose, Command::Reset).unwrap();
+        assert_eq!(the_goose.state().status(), Ok(Status::Playing));
+    }
+
+    #[test]
+    fn test_set_position_does_not_win() {
+        let mut the_goose = the_goose();
+
+        collect(&mut the_goose, Command::Add(name("Pippo"))).ok();
+        collect(&mut the_goose, Command::SetPosition(name("Pippo"), 63)).unwrap();
+
+        assert_eq!(the_goose.state().status(), Ok(Status::Playing));
+    }
+
+    #[test]
+    fn test_rematch_clears_winner() {
+        let mut the_goose = the_goose();
+
+        collect(&mut the_goose, Command::Add(name("Pippo"))).ok();
+        collect(&mut the_goose, Command::SetPosition(name("Pippo"), 60)).unwrap();
+        collect(&mut the_goose, Command::Move(name("Pippo"), [1, 2])).unwrap();
+        assert_eq!(the_goose.state().status(), Ok(Status::Won(name("Pippo"))));
+
+        collect(&mut the_goose, Command::Rematch).unwrap();
+
+        assert_eq!(the_goose.state().status(), Ok(Status::Playing));
+        assert_eq!(
+            the_goose.state().get_player_position(&name("Pippo")),
+            Ok(Some(SamplePosition::from(0)))
+        );
+    }
+
+    #[test]
+    fn test_move_is_transactional() {
+        let mut the_goose = the_goose();
+
+        collect(&mut the_goose, Command::Add(name("Pippo"))).ok();
+        the_goose
+            .state()
+            .connection()
+            .execute_batch(
+                "CREATE TEMP TRIGGER no_jumps BEFORE UPDATE ON players WHEN NEW.position = 12
+                 BEGIN SELECT RAISE(ABORT, 'no jumps'); END",
+            )
+            .unwrap();
+
+        assert!(matches!(
+            collect(&mut the_goose, Command::Move(name("Pippo"), [4, 2])),
+            Err(Error::Inner(_))
+        ));
+        assert_eq!(
+            the_goose.state().get_player_position(&name("Pippo")),
+            Ok(Some(SamplePosition::from(0)))
+        );
+    }
+
+    #[test]
+    fn test_turn() {
+        let mut the_goose = the_goose();
+
+        collect(&mut the_goose, Command::Add(name("Pippo"))).ok();
+        collect(&mut the_goose, Command::Add(name("Pluto"))).ok();
+        assert_eq!(the_goose.state().turn(), Ok(Turn::default()));
+
+        collect(&mut the_goose, Command::Move(name("Pippo"), [1, 2])).unwrap();
+        let pluto = Turn {
+            player: Some(name("Pluto")),
+            extra_turns: 0,
+        };
+        assert_eq!(the_goose.state().turn(), Ok(pluto.clone()));
+
+        the_goose
+            .state()
+            .connection()
+            .execute_batch(
+                "CREATE TEMP TRIGGER no_jumps BEFORE UPDATE ON players WHEN NEW.position = 12
+                 BEGIN SELECT RAISE(ABORT, 'no jumps'); END",
+            )
+            .unwrap();
+        assert!(collect(&mut the_goose, Command::Move(name("Pluto"), [4, 2])).is_err());
+        assert_eq!(the_goose.state().turn(), Ok(pluto));
+    }
+
+    #[test]
+    fn test_rules() {
+        let mut the_goose = the_goose()
+            .with_goose_rule(GooseRule::GooseToGoose)
+            .with_extra_turns(ExtraTurns {
+                doubles: true,
+                ..ExtraTurns::default()
+            });
+
+        collect(&mut the_goose, Command::Add(name("Pippo"))).ok();
+        collect(&mut the_goose, Command::Add(name("Pluto"))).ok();
+
+        let events = collect(&mut the_goose, Command::Move(name("Pippo"), [2, 3])).unwrap();
+        assert!(matches!(
+            events.last(),
+            Some(Event::ExtraTurn(player)) if player == "Pippo"
+        ));
+        assert_eq!(
+            the_goose.state().get_player_position(&name("Pippo")),
+            Ok(Some(SamplePosition::from(9)))
+        );
+
+        let events = collect(&mut the_goose, Command::Move(name("Pippo"), [1, 1])).unwrap();
+        assert!(matches!(
+            events.last(),
+            Some(Event::ExtraTurn(player)) if player == "Pippo"
+        ));
+    }
+
+    #[test]
+    fn test_find_players_by_position_uses_index() {
+        let the_goose = the_goose();
+
+        let plan = the_goose
+            .state()
+            .connection()
+            .query_row(
+                "EXPLAIN QUERY PLAN \
+                 SELECT name FROM players WHERE game_id = ?1 AND position = ?2 ORDER BY name",
+                params![1, 0],
+                |row| row.get::<_, String>(3),
+            )
+            .unwrap();
+
+        assert!(plan.contains("players_by_position"), "{plan}");
+    }
+
+    #[test]
+    fn test_resume_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("the-goose.sqlite");
+
+        let game = {
+            let mut the_goose = SqliteTheGoose::<SamplePosition>::new(
+                SqliteState::open(&path).unwrap(),
+                SampleDie::default(),
+            );
+
+            collect(&mut the_goose, Command::Add(name("Pippo"))).ok();
+            collect(&mut the_goose, Command::Move(name("Pippo"), [2, 2])).unwrap();
+
+            the_goose.into_state().game()
+        };
+
+        let other = SqliteState::<SamplePosition>::open(&path).unwrap();
+        assert_ne!(other.game(), game);
+        assert_eq!(other.players(), Ok(vec![]));
+
+        let state =
+            SqliteState::<SamplePosition>::resume(Connection::open(&path).unwrap(), game).unwrap();
+        assert_eq!(state.players(), Ok(vec![name("Pippo")]));
+        assert_eq!(
+            state.get_player_position(&name("Pippo")),
+            Ok(Some(SamplePosition::from(4)))
+        );
+
+        assert!(
+            SqliteState::<SamplePosition>::resume(Connection::open(&path).unwrap(), 42).is_err()
+        );
+    }
+}
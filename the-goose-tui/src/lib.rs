@@ -0,0 +1,496 @@
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Widget};
+
+use sample::text::{render, render_error};
+use sample::{collect, SampleDie, SamplePosition, SampleTheGoose};
+use the_goose::{Command, Event, ExtraTurns, GooseRule, Position, PositionType, State};
+
+const SIDE: u16 = 8;
+const SQUARES: usize = (SIDE * SIDE) as usize;
+const CELL_WIDTH: u16 = 6;
+const CELL_HEIGHT: u16 = 2;
+
+const PAWNS: [Color; 8] = [
+    Color::Red,
+    Color::Blue,
+    Color::Magenta,
+    Color::Green,
+    Color::LightRed,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::DarkGray,
+];
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Mode {
+    Normal,
+    Adding(String),
+}
+
+struct Snapshot {
+    the_goose: SampleTheGoose<String, SamplePosition>,
+    joined: Vec<String>,
+    log: usize,
+}
+
+pub struct App {
+    the_goose: SampleTheGoose<String, SamplePosition>,
+    joined: Vec<String>,
+    log: Vec<String>,
+    history: Vec<Snapshot>,
+    scroll: u16,
+    mode: Mode,
+    message: Option<String>,
+    running: bool,
+}
+
+pub fn spiral() -> [(u16, u16); SQUARES] {
+    let mut cells = [(0, 0); SQUARES];
+    let (mut top, mut left, mut bottom, mut right) = (0, 0, SIDE - 1, SIDE - 1);
+    let mut square = 0;
+
+    while square < SQUARES {
+        for x in left..=right {
+            cells[square] = (x, top);
+            square += 1;
+        }
+        top += 1;
+        for y in top..=bottom {
+            cells[square] = (right, y);
+            square += 1;
+        }
+        right -= 1;
+        for x in (left..=right).rev() {
+            cells[square] = (x, bottom);
+            square += 1;
+        }
+        bottom -= 1;
+        for y in (top..=bottom).rev() {
+            cells[square] = (left, y);
+            square += 1;
+        }
+        left += 1;
+    }
+
+    cells
+}
+
+fn square_style(position: &SamplePosition) -> Style {
+    match position.get_type() {
+        PositionType::TheGoose => Style::new().fg(Color::Black).bg(Color::Yellow),
+        PositionType::TheBridge => Style::new().fg(Color::Black).bg(Color::Cyan),
+        PositionType::End => Style::new().fg(Color::Black).bg(Color::White),
+        PositionType::Normal => Style::new(),
+    }
+}
+
+fn square_label(position: &SamplePosition) -> String {
+    let marker = match position.get_type() {
+        PositionType::TheGoose => " G",
+        PositionType::TheBridge => " B",
+        PositionType::End => " E",
+        PositionType::Normal => "",
+    };
+
+    format!("{:>2}{marker}", u32::from(*position))
+}
+
+impl App {
+    pub fn new(seed: u64) -> Self {
+        App {
+            the_goose: SampleTheGoose::with_die(SampleDie::new(1..=6, seed)),
+            joined: Vec::new(),
+            log: Vec::new(),
+            history: Vec::new(),
+            scroll: 0,
+            mode: Mode::Normal,
+            message: None,
+            running: true,
+        }
+    }
+
+    pub fn with_goose_rule(mut self, goose_rule: GooseRule) -> Self {
+        self.the_goose = self.the_goose.with_goose_rule(goose_rule);
+        self
+    }
+
+    pub fn with_extra_turns(mut self, extra_turns: ExtraTurns) -> Self {
+        self.the_goose = self.the_goose.with_extra_turns(extra_turns);
+        self
+    }
+
+    pub fn running(&self) -> bool {
+        self.running
+    }
+
+    pub fn mode(&self) -> &Mode {
+        &self.mode
+    }
+
+    pub fn log(&self) -> &[String] {
+        &self.log
+    }
+
+    pub fn position(&self, player: &str) -> Option<u32> {
+        self.the_goose
+            .get(player)
+            .map(|position| (*position).into())
+    }
+
+    pub fn current(&self) -> Option<String> {
+        let turn = self.the_goose.turn().ok()?;
+
+        turn.player
+            .or_else(|| self.the_goose.players().ok()?.into_iter().next())
+    }
+
+    pub fn execute(&mut self, command: Command<String, [u32; 2]>) {
+        let snapshot = Snapshot {
+            the_goose: self.the_goose.clone(),
+            joined: self.joined.clone(),
+            log: self.log.len(),
+        };
+        match collect(&mut self.the_goose, command) {
+            Ok(events) => {
+                for event in events.iter() {
+                    match event {
+                        Event::PlayerJoined(player) => self.joined.push(player.clone()),
+                        Event::PlayerLeft(player) | Event::Kicked(player) => {
+                            self.joined.retain(|p| p != player)
+                        }
+                        Event::PlayerRenamed(player, name) => {
+                            for p in self.joined.iter_mut().filter(|p| *p == player) {
+                                *p = name.clone();
+                            }
+                        }
+                        _ => {}
+                    }
+                    self.log.push(render(core::slice::from_ref(event)));
+                }
+                self.history.push(snapshot);
+                self.scroll = 0;
+            }
+            Err(e) => self.message = Some(render_error(&e)),
+        }
+    }
+
+    pub fn undo(&mut self) {
+        match self.history.pop() {
+            Some(snapshot) => {
+                self.the_goose = snapshot.the_goose;
+                self.joined = snapshot.joined;
+                self.log.truncate(snapshot.log);
+            }
+            None => self.message = Some("nothing to undo".to_string()),
+        }
+    }
+
+    pub fn key(&mut self, code: KeyCode) {
+        self.message = None;
+
+        match (&mut self.mode, code) {
+            (Mode::Normal, KeyCode::Char('q') | KeyCode::Esc) => self.running = false,
+            (Mode::Normal, KeyCode::Char('a')) => self.mode = Mode::Adding(String::new()),
+            (Mode::Normal, KeyCode::Char('u')) => self.undo(),
+            (Mode::Normal, KeyCode::Char('r') | KeyCode::Char(' ')) => match self.current() {
+                Some(player) => self.execute(Command::RollAndMove(player)),
+                None => self.message = Some("add a player first".to_string()),
+            },
+            (Mode::Normal, KeyCode::Up) => self.scroll = self.scroll.saturating_add(1),
+            (Mode::Normal, KeyCode::Down) => self.scroll = self.scroll.saturating_sub(1),
+            (Mode::Adding(name), KeyCode::Char(c)) if !c.is_whitespace() => name.push(c),
+            (Mode::Adding(name), KeyCode::Backspace) => {
+                name.pop();
+            }
+            (Mode::Adding(_), KeyCode::Esc) => self.mode = Mode::Normal,
+            (Mode::Adding(name), KeyCode::Enter) => {
+                let name = std::mem::take(name);
+                self.mode = Mode::Normal;
+                if !name.is_empty() {
+                    self.execute(Command::Add(name));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn colour(&self, player: &String) -> Color {
+        let index = self.joined.iter().position(|p| p == player).unwrap_or(0);
+
+        PAWNS[index % PAWNS.len()]
+    }
+
+    fn render_board(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered().title(" The Goose ");
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        for (square, (x, y)) in spiral().into_iter().enumerate() {
+            let cell = Rect::new(
+                inner.x + x * CELL_WIDTH,
+                inner.y + y * CELL_HEIGHT,
+                CELL_WIDTH - 1,
+                CELL_HEIGHT,
+            );
+            if cell.right() > inner.right() || cell.bottom() > inner.bottom() {
+                continue;
+            }
+
+            let position = SamplePosition::from(square as u32);
+            let style = square_style(&position);
+            buf.set_style(cell, style);
+            buf.set_string(cell.x, cell.y, square_label(&position), style);
+
+            let pawns = self
+                .joined
+                .iter()
+                .filter(|player| self.the_goose.get(*player) == Some(&position))
+                .collect::<Vec<_>>();
+            for (i, player) in pawns.iter().enumerate().take(cell.width as usize) {
+                let (pawn, colour) = if i + 1 == cell.width as usize && pawns.len() > i + 1 {
+                    ('+', style.fg.unwrap_or(Color::Reset))
+                } else {
+                    (player.chars().next().unwrap_or('?'), self.colour(player))
+                };
+                buf.set_string(
+                    cell.x + i as u16,
+                    cell.y + 1,
+                    pawn.to_string(),
+                    style.fg(colour).add_modifier(Modifier::BOLD),
+                );
+            }
+        }
+    }
+
+    fn render_players(&self, area: Rect, buf: &mut Buffer) {
+        let current = self.current();
+        let lines = self
+            .joined
+            .iter()
+            .map(|player| {
+                let marker = if current.as_ref() == Some(player) {
+                    "> "
+                } else {
+                    "  "
+                };
+                Line::from(vec![
+                    Span::raw(marker),
+                    Span::styled(
+                        player.clone(),
+                        Style::new()
+                            .fg(self.colour(player))
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(format!(" {}", self.position(player).unwrap_or_default())),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        Paragraph::new(lines)
+            .block(Block::bordered().title(" Players "))
+            .render(area, buf);
+    }
+
+    fn render_log(&self, area: Rect, buf: &mut Buffer) {
+        let height = area.height.saturating_sub(2);
+        let bottom = (self.log.len() as u16).saturating_sub(height);
+        let offset = bottom.saturating_sub(self.scroll);
+
+        Paragraph::new(
+            self.log
+                .iter()
+                .map(|line| Line::raw(line.as_str()))
+                .collect::<Vec<_>>(),
+        )
+        .block(Block::bordered().title(" Events "))
+        .scroll((offset, 0))
+        .render(area, buf);
+    }
+
+    fn render_status(&self, area: Rect, buf: &mut Buffer) {
+        let status = match (&self.mode, &self.message) {
+            (Mode::Adding(name), _) => Line::raw(format!("player name: {name}_")),
+            (Mode::Normal, Some(message)) => Line::styled(message.as_str(), Color::Red),
+            (Mode::Normal, None) => Line::raw("r roll  a add player  u undo  ↑↓ scroll  q quit"),
+        };
+
+        status.render(area, buf);
+    }
+}
+
+impl Widget for &App {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [main, status] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+        let [board, side] = Layout::horizontal([
+            Constraint::Length(SIDE * CELL_WIDTH + 1),
+            Constraint::Min(0),
+        ])
+        .areas(main);
+        let [players, log] = Layout::vertical([
+            Constraint::Length(self.joined.len() as u16 + 2),
+            Constraint::Min(0),
+        ])
+        .areas(side);
+
+        self.render_board(board, buf);
+        self.render_players(players, buf);
+        self.render_log(log, buf);
+        self.render_status(status, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn name(name: &str) -> String {
+        name.to_string()
+    }
+
+    fn draw(app: &App) -> Buffer {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 100, 20));
+        app.render(buf.area, &mut buf);
+        buf
+    }
+
+    fn row(buf: &Buffer, y: u16) -> String {
+        (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect()
+    }
+
+    fn typing(app: &mut App, keys: &str) {
+        for c in keys.chars() {
+            app.key(KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn test_spiral() {
+        let cells = spiral();
+
+        assert_eq!(u32::from(SamplePosition::end()) as usize, SQUARES - 1);
+        assert_eq!(cells[0], (0, 0));
+        assert_eq!(cells[7], (7, 0));
+        assert_eq!(cells[14], (7, 7));
+        assert_eq!(cells[27], (0, 1));
+        assert_eq!(cells[28], (1, 1));
+        assert_eq!(cells[63], (3, 4));
+        assert!((1..SQUARES).all(|i| !cells[..i].contains(&cells[i])));
+    }
+
+    #[test]
+    fn test_render() {
+        let mut app = App::new(1);
+        app.execute(Command::Add(name("Pippo")));
+        app.execute(Command::Add(name("Pluto")));
+        app.execute(Command::Move(name("Pippo"), [2, 3]));
+
+        let buf = draw(&app);
+
+        assert!(row(&buf, 1)
+            .starts_with("│ 0     1     2     3     4     5 G   6 B   7   ││  Pippo 10"));
+        assert_eq!(buf[(31, 1)].bg, Color::Yellow);
+        assert_eq!(buf[(37, 1)].bg, Color::Cyan);
+
+        let (x, y) = spiral()[10];
+        let pawn = &buf[(1 + x * CELL_WIDTH, 2 + y * CELL_HEIGHT)];
+        assert_eq!((pawn.symbol(), pawn.fg), ("P", Color::Red));
+        let pawn = &buf[(1, 2)];
+        assert_eq!((pawn.symbol(), pawn.fg), ("P", Color::Blue));
+
+        assert!(row(&buf, 2).contains("> Pluto 0"));
+        assert!(row(&buf, 9).contains("Pippo moves again and goes to 10"));
+        assert!(row(&buf, 19).starts_with("r roll"));
+    }
+
+    #[test]
+    fn test_keys() {
+        let mut app = App::new(1);
+
+        app.key(KeyCode::Char('r'));
+        assert_eq!(app.message.as_deref(), Some("add a player first"));
+
+        app.key(KeyCode::Char('a'));
+        typing(&mut app, "Pipp");
+        assert_eq!(app.mode(), &Mode::Adding(name("Pipp")));
+        assert!(row(&draw(&app), 19).starts_with("player name: Pipp_"));
+        typing(&mut app, "o");
+        app.key(KeyCode::Enter);
+        assert_eq!(app.mode(), &Mode::Normal);
+
+        app.key(KeyCode::Char('a'));
+        typing(&mut app, "Pippo");
+        app.key(KeyCode::Enter);
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Pippo: already existing player")
+        );
+        assert!(row(&draw(&app), 19).starts_with("Pippo: already existing player"));
+
+        app.key(KeyCode::Char('r'));
+        assert!(app.log()[1].starts_with("Pippo rolls "));
+        assert_ne!(app.position("Pippo"), Some(0));
+
+        app.key(KeyCode::Char('u'));
+        assert_eq!(app.position("Pippo"), Some(0));
+        assert_eq!(app.log(), [name("Pippo joins the game")]);
+
+        app.key(KeyCode::Char('u'));
+        assert_eq!(app.position("Pippo"), None);
+        app.key(KeyCode::Char('u'));
+        assert_eq!(app.message.as_deref(), Some("nothing to undo"));
+
+        app.key(KeyCode::Char('q'));
+        assert!(!app.running());
+    }
+
+    #[test]
+    fn test_goose_to_goose() {
+        let mut app = App::new(1).with_goose_rule(GooseRule::GooseToGoose);
+        app.execute(Command::Add(name("Pippo")));
+        app.execute(Command::Add(name("Pluto")));
+
+        app.execute(Command::Move(name("Pippo"), [2, 3]));
+        assert_eq!(app.position("Pippo"), Some(9));
+        assert_eq!(app.current(), Some(name("Pippo")));
+        assert_eq!(
+            app.log()[app.log().len() - 2..],
+            [
+                name("From goose to goose, Pippo flies to 9, The Goose"),
+                name("Pippo rolls again")
+            ]
+        );
+
+        app.execute(Command::Move(name("Pippo"), [1, 2]));
+        assert_eq!(app.position("Pippo"), Some(12));
+        assert_eq!(app.current(), Some(name("Pluto")));
+    }
+
+    #[test]
+    fn test_turn() {
+        let mut app = App::new(1);
+        app.execute(Command::Add(name("Pluto")));
+        app.execute(Command::Add(name("Pippo")));
+        app.execute(Command::Add(name("Paperino")));
+        assert_eq!(app.current(), Some(name("Paperino")));
+
+        app.key(KeyCode::Char('r'));
+        assert_eq!(app.current(), Some(name("Pippo")));
+        app.key(KeyCode::Tab);
+        assert_eq!(app.current(), Some(name("Pippo")));
+
+        app.execute(Command::Move(name("Pluto"), [1, 2]));
+        assert_eq!(app.message.as_deref(), Some("Pluto: not your turn"));
+
+        app.execute(Command::Remove(name("Pippo")));
+        assert_eq!(app.current(), Some(name("Pluto")));
+        assert!(row(&draw(&app), 1).contains("> Pluto 0"));
+
+        app.key(KeyCode::Char('u'));
+        assert_eq!(app.current(), Some(name("Pippo")));
+    }
+}
@@ -0,0 +1,56 @@
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use ratatui::DefaultTerminal;
+
+use the_goose::{ExtraTurns, GooseRule};
+use the_goose_tui::App;
+
+fn run(terminal: &mut DefaultTerminal, mut app: App) -> io::Result<()> {
+    while app.running() {
+        terminal.draw(|frame| frame.render_widget(&app, frame.area()))?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                app.key(key.code);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> io::Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let seed = args
+        .iter()
+        .find_map(|arg| arg.parse().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |time| time.as_nanos() as u64)
+        });
+    let spanish = args.iter().any(|arg| arg == "--spanish");
+    let goose_rule = if spanish {
+        GooseRule::GooseToGoose
+    } else {
+        GooseRule::MoveAgain
+    };
+    let extra_turns = ExtraTurns {
+        doubles: args.iter().any(|arg| arg == "--doubles"),
+        dice: spanish,
+        ..ExtraTurns::default()
+    };
+
+    let mut terminal = ratatui::init();
+    let result = run(
+        &mut terminal,
+        App::new(seed)
+            .with_goose_rule(goose_rule)
+            .with_extra_turns(extra_turns),
+    );
+    ratatui::restore();
+
+    result
+}
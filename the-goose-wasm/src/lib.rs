@@ -0,0 +1,231 @@
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use sample::text::render_error;
+use sample::{collect, SampleDie, SamplePosition, SampleTheGoose};
+use the_goose::{Command, Event, EventKind, Landing};
+
+type GameEvent = Event<String, SamplePosition, [u32; 2]>;
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct JsEvent {
+    kind: EventKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    player: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    previous: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    roll: Option<[u32; 2]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    landing: Option<Landing>,
+}
+
+impl From<GameEvent> for JsEvent {
+    fn from(event: GameEvent) -> Self {
+        let mut js = JsEvent {
+            kind: event.kind(),
+            player: event.player().cloned(),
+            previous: None,
+            roll: None,
+            from: None,
+            to: None,
+            landing: None,
+        };
+
+        match event {
+            Event::PlayerRenamed(previous, _) => js.previous = Some(previous),
+            Event::Roll(_, roll) => js.roll = Some(roll),
+            Event::Moved(_, from, to, landing)
+            | Event::MovedAgain(_, from, to, landing)
+            | Event::GooseToGoose(_, from, to, landing) => {
+                js.from = Some(from.into());
+                js.to = Some(to.into());
+                js.landing = Some(landing);
+            }
+            Event::Prank(_, from, to) => {
+                js.from = Some(from.into());
+                js.to = Some(to.into());
+            }
+            Event::Jump(_, to, landing) => {
+                js.to = Some(to.into());
+                js.landing = Some(landing);
+            }
+            Event::Return(_, to) | Event::PositionSet(_, to) => js.to = Some(to.into()),
+            Event::PlayerJoined(_)
+            | Event::PlayerLeft(_)
+            | Event::Bounced(_)
+            | Event::Win(_)
+            | Event::ExtraTurn(_)
+            | Event::Kicked(_)
+            | Event::Reset
+            | Event::Rematch => {}
+        }
+
+        js
+    }
+}
+
+#[wasm_bindgen]
+pub struct Game {
+    the_goose: SampleTheGoose<String, SamplePosition>,
+}
+
+impl Game {
+    fn execute(&mut self, command: Command<String, [u32; 2]>) -> Result<Vec<JsEvent>, String> {
+        match collect(&mut self.the_goose, command) {
+            Ok(events) => Ok(events.iter().cloned().map(JsEvent::from).collect()),
+            Err(e) => Err(render_error(&e)),
+        }
+    }
+
+    fn js(&mut self, command: Command<String, [u32; 2]>) -> Result<JsValue, JsError> {
+        let events = self.execute(command).map_err(|e| JsError::new(&e))?;
+
+        Ok(serde_wasm_bindgen::to_value(&events)?)
+    }
+}
+
+#[wasm_bindgen]
+impl Game {
+    #[wasm_bindgen(constructor)]
+    pub fn new(seed: u32) -> Game {
+        Game {
+            the_goose: SampleTheGoose::with_die(SampleDie::new(1..=6, seed.into())),
+        }
+    }
+
+    #[wasm_bindgen(js_name = addPlayer)]
+    pub fn add_player(&mut self, name: String) -> Result<JsValue, JsError> {
+        self.js(Command::Add(name))
+    }
+
+    #[wasm_bindgen(js_name = removePlayer)]
+    pub fn remove_player(&mut self, name: String) -> Result<JsValue, JsError> {
+        self.js(Command::Remove(name))
+    }
+
+    #[wasm_bindgen(js_name = renamePlayer)]
+    pub fn rename_player(&mut self, name: String, new_name: String) -> Result<JsValue, JsError> {
+        self.js(Command::Rename(name, new_name))
+    }
+
+    #[wasm_bindgen(js_name = movePlayer)]
+    pub fn move_player(
+        &mut self,
+        name: String,
+        first: u32,
+        second: u32,
+    ) -> Result<JsValue, JsError> {
+        self.js(Command::Move(name, [first, second]))
+    }
+
+    #[wasm_bindgen(js_name = rollAndMove)]
+    pub fn roll_and_move(&mut self, name: String) -> Result<JsValue, JsError> {
+        self.js(Command::RollAndMove(name))
+    }
+
+    pub fn position(&self, name: &str) -> Option<u32> {
+        self.the_goose.get(name).map(|position| (*position).into())
+    }
+
+    pub fn players(&self) -> Vec<String> {
+        self.the_goose.keys().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use the_goose::{Cause, PositionType};
+
+    fn name(name: &str) -> String {
+        name.to_string()
+    }
+
+    #[test]
+    fn test_execute() {
+        let mut game = Game::new(1);
+
+        assert_eq!(
+            game.execute(Command::Add(name("Pippo"))),
+            Ok(vec![JsEvent {
+                kind: EventKind::PlayerJoined,
+                player: Some(name("Pippo")),
+                previous: None,
+                roll: None,
+                from: None,
+                to: None,
+                landing: None,
+            }])
+        );
+        assert_eq!(
+            game.execute(Command::Add(name("Pippo"))),
+            Err(name("Pippo: already existing player"))
+        );
+
+        assert_eq!(
+            game.execute(Command::Move(name("Pippo"), [1, 2])),
+            Ok(vec![
+                JsEvent {
+                    kind: EventKind::Roll,
+                    player: Some(name("Pippo")),
+                    previous: None,
+                    roll: Some([1, 2]),
+                    from: None,
+                    to: None,
+                    landing: None,
+                },
+                JsEvent {
+                    kind: EventKind::Moved,
+                    player: Some(name("Pippo")),
+                    previous: None,
+                    roll: None,
+                    from: Some(0),
+                    to: Some(3),
+                    landing: Some(Landing {
+                        square: PositionType::Normal,
+                        cause: Cause::Roll,
+                        sequence: 0,
+                    }),
+                },
+            ])
+        );
+        assert_eq!(game.position("Pippo"), Some(3));
+        assert_eq!(game.players(), vec![name("Pippo")]);
+
+        assert_eq!(
+            game.execute(Command::Rename(name("Pippo"), name("Pluto"))),
+            Ok(vec![JsEvent {
+                kind: EventKind::PlayerRenamed,
+                player: Some(name("Pluto")),
+                previous: Some(name("Pippo")),
+                roll: None,
+                from: None,
+                to: None,
+                landing: None,
+            }])
+        );
+        assert_eq!(game.position("Pluto"), Some(3));
+        assert_eq!(game.players(), vec![name("Pluto")]);
+    }
+
+    #[test]
+    fn test_seeded_rolls() {
+        let play = |seed| {
+            let mut game = Game::new(seed);
+            game.execute(Command::Add(name("Pippo"))).ok();
+
+            (0..10)
+                .flat_map(|_| game.execute(Command::RollAndMove(name("Pippo"))).unwrap())
+                .filter_map(|event| event.roll)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(play(7), play(7));
+        assert_ne!(play(7), play(8));
+    }
+}
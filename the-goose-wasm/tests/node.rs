@@ -0,0 +1,39 @@
+#![cfg(target_arch = "wasm32")]
+
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+use the_goose_wasm::Game;
+
+fn field(value: &JsValue, key: &str) -> JsValue {
+    js_sys::Reflect::get(value, &key.into()).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_game() {
+    let mut game = Game::new(1);
+
+    let events = game.add_player("Pippo".to_string()).unwrap();
+    let event = js_sys::Array::from(&events).get(0);
+    assert_eq!(field(&event, "kind"), JsValue::from("PlayerJoined"));
+
+    assert!(game.add_player("Pippo".to_string()).is_err());
+
+    let events = js_sys::Array::from(&game.move_player("Pippo".to_string(), 1, 2).unwrap());
+    assert_eq!(events.length(), 2);
+    assert_eq!(field(&events.get(1), "to"), JsValue::from(3));
+    let landing = field(&events.get(1), "landing");
+    assert_eq!(field(&landing, "square"), JsValue::from("Normal"));
+    assert_eq!(field(&landing, "sequence"), JsValue::from(0));
+
+    assert!(game.roll_and_move("Pippo".to_string()).is_ok());
+
+    let events = game
+        .rename_player("Pippo".to_string(), "Pluto".to_string())
+        .unwrap();
+    let event = js_sys::Array::from(&events).get(0);
+    assert_eq!(field(&event, "previous"), JsValue::from("Pippo"));
+
+    assert!(game.remove_player("Pluto".to_string()).is_ok());
+    assert_eq!(game.position("Pluto"), None);
+}
@@ -0,0 +1,281 @@
+use core::convert::Infallible;
+use core::future::{ready, Future};
+
+use crate::engine::{self, Awaiting};
+use crate::{Command, Error, Event, ExtraTurns, GooseRule, Position, Roll, Turn};
+
+pub trait Events<Player, Position, Roll> {
+    type Error;
+
+    fn notify(
+        &mut self,
+        event: Event<Player, Position, Roll>,
+    ) -> impl Future<Output = Result<(), Self::Error>>;
+
+    fn begin(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(Ok(()))
+    }
+
+    fn commit(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(Ok(()))
+    }
+
+    fn rollback(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(Ok(()))
+    }
+}
+
+pub trait State<Player, Position, II: IntoIterator<Item = Player>> {
+    type Error;
+
+    fn get_player_position(
+        &self,
+        player: &Player,
+    ) -> impl Future<Output = Result<Option<Position>, Self::Error>>;
+    fn add_player(&mut self, player: Player) -> impl Future<Output = Result<(), Self::Error>>;
+    fn remove_player(&mut self, player: &Player) -> impl Future<Output = Result<(), Self::Error>>;
+    fn find_players_by_position(
+        &self,
+        position: &Position,
+    ) -> impl Future<Output = Result<II, Self::Error>>;
+    fn players(&self) -> impl Future<Output = Result<II, Self::Error>>;
+    fn update_player_position(
+        &mut self,
+        player: &Player,
+        position: &Position,
+    ) -> impl Future<Output = Result<(), Self::Error>>;
+
+    fn begin(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(Ok(()))
+    }
+
+    fn commit(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(Ok(()))
+    }
+
+    fn rollback(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(Ok(()))
+    }
+
+    fn turn(&self) -> impl Future<Output = Result<Turn<Player>, Self::Error>> {
+        ready(Ok(Turn::default()))
+    }
+
+    fn update_turn(
+        &mut self,
+        _turn: Turn<Player>,
+    ) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(Ok(()))
+    }
+
+    fn update_winner(
+        &mut self,
+        _winner: Option<&Player>,
+    ) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(Ok(()))
+    }
+}
+
+pub trait TheGoose<Player, P, R: Roll, II: IntoIterator<Item = Player>> {
+    type State: State<Player, P, II>;
+
+    #[allow(clippy::type_complexity)]
+    fn execute<E: Events<Player, P, R>>(
+        &mut self,
+        command: Command<Player, R>,
+        events: &mut E,
+    ) -> impl Future<
+        Output = Result<
+            (),
+            Error<
+                Player,
+                R::Face,
+                <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error,
+                E::Error,
+            >,
+        >,
+    >
+    where
+        Player: Clone + PartialEq,
+        R::Face: Clone + PartialEq,
+        R::Step: Copy,
+        P: Position<R::Step> + Copy,
+    {
+        async move { engine::execute(&mut Awaiting(self), command, events).await }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn add_player(
+        &mut self,
+        player: Player,
+    ) -> impl Future<
+        Output = Result<
+            (),
+            Error<
+                Player,
+                R::Face,
+                <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error,
+                Infallible,
+            >,
+        >,
+    > {
+        async move { engine::add_player(&mut Awaiting(self), player).await }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn remove_player(
+        &mut self,
+        player: &Player,
+    ) -> impl Future<
+        Output = Result<
+            (),
+            Error<
+                Player,
+                R::Face,
+                <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error,
+                Infallible,
+            >,
+        >,
+    >
+    where
+        Player: Clone + PartialEq,
+    {
+        async move { engine::remove_player(&mut Awaiting(self), player).await }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn rename_player(
+        &mut self,
+        player: &Player,
+        name: Player,
+    ) -> impl Future<
+        Output = Result<
+            (),
+            Error<
+                Player,
+                R::Face,
+                <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error,
+                Infallible,
+            >,
+        >,
+    >
+    where
+        Player: Clone + PartialEq,
+    {
+        async move { engine::rename_player(&mut Awaiting(self), player, name).await }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn set_player_position(
+        &mut self,
+        player: &Player,
+        position: &P,
+    ) -> impl Future<
+        Output = Result<
+            (),
+            Error<
+                Player,
+                R::Face,
+                <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error,
+                Infallible,
+            >,
+        >,
+    >
+    where
+        Player: Clone,
+        P: Position<R::Step> + Copy,
+    {
+        async move {
+            engine::set_player_position(&mut Awaiting(self), player, (*position).into())
+                .await
+                .map(|_| ())
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn reset(
+        &mut self,
+    ) -> impl Future<
+        Output = Result<
+            (),
+            Error<
+                Player,
+                R::Face,
+                <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error,
+                Infallible,
+            >,
+        >,
+    >
+    where
+        P: From<u32>,
+    {
+        async move { engine::reset(&mut Awaiting(self)).await }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn move_player<E: Events<Player, P, R>>(
+        &mut self,
+        player: &Player,
+        roll: R,
+        events: &mut E,
+    ) -> impl Future<
+        Output = Result<
+            (),
+            Error<
+                Player,
+                R::Face,
+                <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error,
+                E::Error,
+            >,
+        >,
+    >
+    where
+        R::Face: Clone + PartialEq,
+        R::Step: Copy,
+        P: Position<R::Step> + Copy,
+        Player: Clone + PartialEq,
+    {
+        async move { engine::move_player(&mut Awaiting(self), player, roll, events).await }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn roll_and_move_player<E: Events<Player, P, R>>(
+        &mut self,
+        player: &Player,
+        events: &mut E,
+    ) -> impl Future<
+        Output = Result<
+            (),
+            Error<
+                Player,
+                R::Face,
+                <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error,
+                E::Error,
+            >,
+        >,
+    >
+    where
+        R::Face: Clone + PartialEq,
+        R::Step: Copy,
+        P: Position<R::Step> + Copy,
+        Player: Clone + PartialEq,
+    {
+        async move { engine::roll_and_move_player(&mut Awaiting(self), player, events).await }
+    }
+
+    fn state(&self) -> &Self::State;
+
+    fn state_mut(&mut self) -> &mut Self::State;
+
+    fn roll_dice(&mut self) -> R;
+
+    fn is_valid_face(&self, face: &R::Face) -> bool;
+
+    fn goose_rule(&self) -> GooseRule {
+        GooseRule::MoveAgain
+    }
+
+    fn extra_turns(&self) -> ExtraTurns {
+        ExtraTurns::default()
+    }
+}
@@ -0,0 +1,708 @@
+use core::future::{ready, Future};
+use core::pin::pin;
+use core::task::{Context, Poll, Waker};
+
+use crate::asynch::{self, Events};
+use crate::{
+    AddPosition, Cause, Command, Error, Event, ExtraTurns, GooseRule, Landing, Position,
+    PositionType, Roll, Turn,
+};
+
+pub(crate) trait Host<Player, P, R: Roll, II: IntoIterator<Item = Player>> {
+    type Error;
+
+    fn get_player_position(
+        &self,
+        player: &Player,
+    ) -> impl Future<Output = Result<Option<P>, Self::Error>>;
+    fn add_player(&mut self, player: Player) -> impl Future<Output = Result<(), Self::Error>>;
+    fn remove_player(&mut self, player: &Player) -> impl Future<Output = Result<(), Self::Error>>;
+    fn find_players_by_position(
+        &self,
+        position: &P,
+    ) -> impl Future<Output = Result<II, Self::Error>>;
+    fn players(&self) -> impl Future<Output = Result<II, Self::Error>>;
+    fn update_player_position(
+        &mut self,
+        player: &Player,
+        position: &P,
+    ) -> impl Future<Output = Result<(), Self::Error>>;
+    fn begin(&mut self) -> impl Future<Output = Result<(), Self::Error>>;
+    fn commit(&mut self) -> impl Future<Output = Result<(), Self::Error>>;
+    fn rollback(&mut self) -> impl Future<Output = Result<(), Self::Error>>;
+    fn turn(&self) -> impl Future<Output = Result<Turn<Player>, Self::Error>>;
+    fn update_turn(&mut self, turn: Turn<Player>) -> impl Future<Output = Result<(), Self::Error>>;
+    fn update_winner(
+        &mut self,
+        winner: Option<&Player>,
+    ) -> impl Future<Output = Result<(), Self::Error>>;
+    fn roll_dice(&mut self) -> R;
+    fn is_valid_face(&self, face: &R::Face) -> bool;
+    fn goose_rule(&self) -> GooseRule;
+    fn extra_turns(&self) -> ExtraTurns;
+}
+
+pub(crate) struct Blocking<'a, T: ?Sized>(pub(crate) &'a mut T);
+
+impl<Player, P, R, II, G> Host<Player, P, R, II> for Blocking<'_, G>
+where
+    R: Roll,
+    II: IntoIterator<Item = Player>,
+    G: crate::TheGoose<Player, P, R, II> + ?Sized,
+{
+    type Error = <G::State as crate::State<Player, P, II>>::Error;
+
+    fn get_player_position(
+        &self,
+        player: &Player,
+    ) -> impl Future<Output = Result<Option<P>, Self::Error>> {
+        ready(crate::State::get_player_position(self.0.state(), player))
+    }
+
+    fn add_player(&mut self, player: Player) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(crate::State::add_player(self.0.state_mut(), player))
+    }
+
+    fn remove_player(&mut self, player: &Player) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(crate::State::remove_player(self.0.state_mut(), player))
+    }
+
+    fn find_players_by_position(
+        &self,
+        position: &P,
+    ) -> impl Future<Output = Result<II, Self::Error>> {
+        ready(crate::State::find_players_by_position(
+            self.0.state(),
+            position,
+        ))
+    }
+
+    fn players(&self) -> impl Future<Output = Result<II, Self::Error>> {
+        ready(crate::State::players(self.0.state()))
+    }
+
+    fn update_player_position(
+        &mut self,
+        player: &Player,
+        position: &P,
+    ) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(crate::State::update_player_position(
+            self.0.state_mut(),
+            player,
+            position,
+        ))
+    }
+
+    fn begin(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(crate::State::begin(self.0.state_mut()))
+    }
+
+    fn commit(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(crate::State::commit(self.0.state_mut()))
+    }
+
+    fn rollback(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(crate::State::rollback(self.0.state_mut()))
+    }
+
+    fn turn(&self) -> impl Future<Output = Result<Turn<Player>, Self::Error>> {
+        ready(crate::State::turn(self.0.state()))
+    }
+
+    fn update_turn(&mut self, turn: Turn<Player>) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(crate::State::update_turn(self.0.state_mut(), turn))
+    }
+
+    fn update_winner(
+        &mut self,
+        winner: Option<&Player>,
+    ) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(crate::State::update_winner(self.0.state_mut(), winner))
+    }
+
+    fn roll_dice(&mut self) -> R {
+        self.0.roll_dice()
+    }
+
+    fn is_valid_face(&self, face: &R::Face) -> bool {
+        self.0.is_valid_face(face)
+    }
+
+    fn goose_rule(&self) -> GooseRule {
+        self.0.goose_rule()
+    }
+
+    fn extra_turns(&self) -> ExtraTurns {
+        self.0.extra_turns()
+    }
+}
+
+impl<Player, P, R, E> Events<Player, P, R> for Blocking<'_, E>
+where
+    E: crate::Events<Player, P, R> + ?Sized,
+{
+    type Error = E::Error;
+
+    fn notify(
+        &mut self,
+        event: Event<Player, P, R>,
+    ) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(self.0.notify(event))
+    }
+
+    fn begin(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(self.0.begin())
+    }
+
+    fn commit(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(self.0.commit())
+    }
+
+    fn rollback(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        ready(self.0.rollback())
+    }
+}
+
+pub(crate) struct Awaiting<'a, T: ?Sized>(pub(crate) &'a mut T);
+
+impl<'a, Player, P, R, II, G> Host<Player, P, R, II> for Awaiting<'a, G>
+where
+    R: Roll,
+    II: IntoIterator<Item = Player>,
+    G: asynch::TheGoose<Player, P, R, II> + ?Sized,
+    G::State: 'a,
+{
+    type Error = <G::State as asynch::State<Player, P, II>>::Error;
+
+    fn get_player_position(
+        &self,
+        player: &Player,
+    ) -> impl Future<Output = Result<Option<P>, Self::Error>> {
+        asynch::State::get_player_position(self.0.state(), player)
+    }
+
+    fn add_player(&mut self, player: Player) -> impl Future<Output = Result<(), Self::Error>> {
+        asynch::State::add_player(self.0.state_mut(), player)
+    }
+
+    fn remove_player(&mut self, player: &Player) -> impl Future<Output = Result<(), Self::Error>> {
+        asynch::State::remove_player(self.0.state_mut(), player)
+    }
+
+    fn find_players_by_position(
+        &self,
+        position: &P,
+    ) -> impl Future<Output = Result<II, Self::Error>> {
+        asynch::State::find_players_by_position(self.0.state(), position)
+    }
+
+    fn players(&self) -> impl Future<Output = Result<II, Self::Error>> {
+        asynch::State::players(self.0.state())
+    }
+
+    fn update_player_position(
+        &mut self,
+        player: &Player,
+        position: &P,
+    ) -> impl Future<Output = Result<(), Self::Error>> {
+        asynch::State::update_player_position(self.0.state_mut(), player, position)
+    }
+
+    fn begin(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        asynch::State::begin(self.0.state_mut())
+    }
+
+    fn commit(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        asynch::State::commit(self.0.state_mut())
+    }
+
+    fn rollback(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        asynch::State::rollback(self.0.state_mut())
+    }
+
+    fn turn(&self) -> impl Future<Output = Result<Turn<Player>, Self::Error>> {
+        asynch::State::turn(self.0.state())
+    }
+
+    fn update_turn(&mut self, turn: Turn<Player>) -> impl Future<Output = Result<(), Self::Error>> {
+        asynch::State::update_turn(self.0.state_mut(), turn)
+    }
+
+    fn update_winner(
+        &mut self,
+        winner: Option<&Player>,
+    ) -> impl Future<Output = Result<(), Self::Error>> {
+        asynch::State::update_winner(self.0.state_mut(), winner)
+    }
+
+    fn roll_dice(&mut self) -> R {
+        self.0.roll_dice()
+    }
+
+    fn is_valid_face(&self, face: &R::Face) -> bool {
+        self.0.is_valid_face(face)
+    }
+
+    fn goose_rule(&self) -> GooseRule {
+        self.0.goose_rule()
+    }
+
+    fn extra_turns(&self) -> ExtraTurns {
+        self.0.extra_turns()
+    }
+}
+
+// Every future built on `Blocking` is ready on the first poll.
+pub(crate) fn now<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => unreachable!(),
+    }
+}
+
+pub(crate) async fn execute<Player, P, R, II, H, E>(
+    host: &mut H,
+    command: Command<Player, R>,
+    events: &mut E,
+) -> Result<(), Error<Player, R::Face, H::Error, E::Error>>
+where
+    R: Roll,
+    II: IntoIterator<Item = Player>,
+    H: Host<Player, P, R, II>,
+    E: Events<Player, P, R>,
+    Player: Clone + PartialEq,
+    R::Face: Clone + PartialEq,
+    R::Step: Copy,
+    P: Position<R::Step> + Copy,
+{
+    host.begin().await?;
+    if let Err(e) = events.begin().await {
+        host.rollback().await.ok();
+        return Err(Error::Events(e));
+    }
+
+    if let Err(e) = apply(host, command, events).await {
+        events.rollback().await.ok();
+        host.rollback().await.ok();
+        return Err(e);
+    }
+
+    // The events commit first so a sink that fails can still undo the state; a state
+    // that fails to commit after them is the one failure left unrecoverable.
+    if let Err(e) = events.commit().await {
+        host.rollback().await.ok();
+        return Err(Error::Events(e));
+    }
+
+    Ok(host.commit().await?)
+}
+
+async fn apply<Player, P, R, II, H, E>(
+    host: &mut H,
+    command: Command<Player, R>,
+    events: &mut E,
+) -> Result<(), Error<Player, R::Face, H::Error, E::Error>>
+where
+    R: Roll,
+    II: IntoIterator<Item = Player>,
+    H: Host<Player, P, R, II>,
+    E: Events<Player, P, R>,
+    Player: Clone + PartialEq,
+    R::Face: Clone + PartialEq,
+    R::Step: Copy,
+    P: Position<R::Step> + Copy,
+{
+    let event = match command {
+        Command::Add(player) => {
+            add_player(host, player.clone()).await?;
+            Event::PlayerJoined(player)
+        }
+        Command::Remove(player) => {
+            remove_player(host, &player).await?;
+            Event::PlayerLeft(player)
+        }
+        Command::Move(player, roll) => return move_player(host, &player, roll, events).await,
+        Command::RollAndMove(player) => return roll_and_move_player(host, &player, events).await,
+        Command::SetPosition(player, position) => {
+            let position = set_player_position(host, &player, position).await?;
+            Event::PositionSet(player, position)
+        }
+        Command::Kick(player) => {
+            remove_player(host, &player).await?;
+            Event::Kicked(player)
+        }
+        Command::Rename(player, name) => {
+            rename_player(host, &player, name.clone()).await?;
+            Event::PlayerRenamed(player, name)
+        }
+        Command::Reset => {
+            reset(host).await?;
+            host.update_winner(None).await?;
+            Event::Reset
+        }
+        Command::Rematch => {
+            reset(host).await?;
+            host.update_winner(None).await?;
+            host.update_turn(Turn::default()).await?;
+            Event::Rematch
+        }
+    };
+
+    events.notify(event).await.map_err(Error::Events)
+}
+
+pub(crate) async fn add_player<Player, P, R, II, H, EventsError>(
+    host: &mut H,
+    player: Player,
+) -> Result<(), Error<Player, R::Face, H::Error, EventsError>>
+where
+    R: Roll,
+    II: IntoIterator<Item = Player>,
+    H: Host<Player, P, R, II>,
+{
+    if host.get_player_position(&player).await?.is_none() {
+        Ok(host.add_player(player).await?)
+    } else {
+        Err(Error::DuplicatePlayer(player))
+    }
+}
+
+pub(crate) async fn remove_player<Player, P, R, II, H, EventsError>(
+    host: &mut H,
+    player: &Player,
+) -> Result<(), Error<Player, R::Face, H::Error, EventsError>>
+where
+    R: Roll,
+    II: IntoIterator<Item = Player>,
+    H: Host<Player, P, R, II>,
+    Player: Clone + PartialEq,
+{
+    if host.get_player_position(player).await?.is_none() {
+        return Err(Error::PlayerNotFound(player.clone()));
+    }
+
+    if host.turn().await?.player.as_ref() == Some(player) {
+        let next = next_player(host, player).await?.filter(|p| p != player);
+        host.update_turn(Turn {
+            player: next,
+            extra_turns: 0,
+        })
+        .await?;
+    }
+
+    Ok(host.remove_player(player).await?)
+}
+
+pub(crate) async fn rename_player<Player, P, R, II, H, EventsError>(
+    host: &mut H,
+    player: &Player,
+    name: Player,
+) -> Result<(), Error<Player, R::Face, H::Error, EventsError>>
+where
+    R: Roll,
+    II: IntoIterator<Item = Player>,
+    H: Host<Player, P, R, II>,
+    Player: Clone + PartialEq,
+{
+    let position = host
+        .get_player_position(player)
+        .await?
+        .ok_or_else(|| Error::PlayerNotFound(player.clone()))?;
+    if host.get_player_position(&name).await?.is_some() {
+        return Err(Error::DuplicatePlayer(name));
+    }
+
+    host.remove_player(player).await?;
+    host.add_player(name.clone()).await?;
+    host.update_player_position(&name, &position).await?;
+
+    let turn = host.turn().await?;
+    if turn.player.as_ref() == Some(player) {
+        host.update_turn(Turn {
+            player: Some(name),
+            ..turn
+        })
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_player_position<Player, P, R, II, H, EventsError>(
+    host: &mut H,
+    player: &Player,
+    position: u32,
+) -> Result<P, Error<Player, R::Face, H::Error, EventsError>>
+where
+    R: Roll,
+    II: IntoIterator<Item = Player>,
+    H: Host<Player, P, R, II>,
+    Player: Clone,
+    P: Position<R::Step> + Copy,
+{
+    if host.get_player_position(player).await?.is_none() {
+        return Err(Error::PlayerNotFound(player.clone()));
+    }
+
+    if position > P::end().into() {
+        return Err(Error::InvalidPosition(player.clone(), position));
+    }
+
+    let position = P::from(position);
+    host.update_player_position(player, &position).await?;
+
+    Ok(position)
+}
+
+pub(crate) async fn reset<Player, P, R, II, H, EventsError>(
+    host: &mut H,
+) -> Result<(), Error<Player, R::Face, H::Error, EventsError>>
+where
+    R: Roll,
+    II: IntoIterator<Item = Player>,
+    H: Host<Player, P, R, II>,
+    P: From<u32>,
+{
+    let start = P::from(0);
+
+    for player in host.players().await? {
+        host.update_player_position(&player, &start).await?;
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn move_player<Player, P, R, II, H, E>(
+    host: &mut H,
+    player: &Player,
+    roll: R,
+    events: &mut E,
+) -> Result<(), Error<Player, R::Face, H::Error, E::Error>>
+where
+    R: Roll,
+    II: IntoIterator<Item = Player>,
+    H: Host<Player, P, R, II>,
+    E: Events<Player, P, R>,
+    Player: Clone + PartialEq,
+    R::Face: Clone + PartialEq,
+    R::Step: Copy,
+    P: Position<R::Step> + Copy,
+{
+    let initial_position = host
+        .get_player_position(player)
+        .await?
+        .ok_or_else(|| Error::PlayerNotFound(player.clone()))?;
+
+    let turn = host.turn().await?;
+    if turn.player.as_ref().is_some_and(|p| p != player) {
+        return Err(Error::NotYourTurn(player.clone()));
+    }
+
+    if let Some(face) = roll.faces().iter().find(|face| !host.is_valid_face(face)) {
+        return Err(Error::InvalidRoll(player.clone(), face.clone()));
+    }
+
+    let mut start_position = initial_position;
+
+    let steps = roll.steps();
+    let doubles =
+        roll.faces().len() > 1 && roll.faces().windows(2).all(|faces| faces[0] == faces[1]);
+
+    let mut again = false;
+    let mut goose_to_goose = false;
+    let mut won = false;
+    let mut sequence = 0;
+    events
+        .notify(Event::Roll(player.clone(), roll))
+        .await
+        .map_err(Error::Events)?;
+    loop {
+        let (moved_position, end_position) = match start_position.add(steps) {
+            AddPosition::Bounced(bounced_position, end_position) => {
+                (end_position, Some(bounced_position))
+            }
+            AddPosition::Normal(end_position) => (end_position, None),
+        };
+
+        let landing = Landing {
+            square: end_position.unwrap_or(moved_position).get_type(),
+            cause: if again { Cause::TheGoose } else { Cause::Roll },
+            sequence,
+        };
+        sequence += 1;
+        events
+            .notify(if again {
+                Event::MovedAgain(player.clone(), start_position, moved_position, landing)
+            } else {
+                Event::Moved(player.clone(), start_position, moved_position, landing)
+            })
+            .await
+            .map_err(Error::Events)?;
+
+        let end_position = match end_position {
+            Some(bounced_position) => {
+                events
+                    .notify(Event::Bounced(player.clone()))
+                    .await
+                    .map_err(Error::Events)?;
+                events
+                    .notify(Event::Return(player.clone(), bounced_position))
+                    .await
+                    .map_err(Error::Events)?;
+                bounced_position
+            }
+            None => moved_position,
+        };
+
+        let players = host.find_players_by_position(&end_position).await?;
+
+        start_position = end_position;
+
+        host.update_player_position(player, &start_position).await?;
+
+        for p in players.into_iter().filter(|p| p != player) {
+            events
+                .notify(Event::Prank(p.clone(), end_position, initial_position))
+                .await
+                .map_err(Error::Events)?;
+            host.update_player_position(&p, &initial_position).await?;
+        }
+
+        match start_position.get_type() {
+            PositionType::TheBridge => {
+                start_position = 12.into();
+                host.update_player_position(player, &start_position).await?;
+                let landing = Landing {
+                    square: start_position.get_type(),
+                    cause: Cause::TheBridge,
+                    sequence,
+                };
+                events
+                    .notify(Event::Jump(player.clone(), start_position, landing))
+                    .await
+                    .map_err(Error::Events)?;
+                break;
+            }
+            PositionType::TheGoose => {
+                if let GooseRule::GooseToGoose = host.goose_rule() {
+                    let Some(next_goose) = start_position.next_goose() else {
+                        goose_to_goose = true;
+                        break;
+                    };
+                    let players = host.find_players_by_position(&next_goose).await?;
+
+                    host.update_player_position(player, &next_goose).await?;
+                    let landing = Landing {
+                        square: next_goose.get_type(),
+                        cause: Cause::GooseToGoose,
+                        sequence,
+                    };
+                    events
+                        .notify(Event::GooseToGoose(
+                            player.clone(),
+                            start_position,
+                            next_goose,
+                            landing,
+                        ))
+                        .await
+                        .map_err(Error::Events)?;
+
+                    for p in players.into_iter().filter(|p| p != player) {
+                        events
+                            .notify(Event::Prank(p.clone(), next_goose, initial_position))
+                            .await
+                            .map_err(Error::Events)?;
+                        host.update_player_position(&p, &initial_position).await?;
+                    }
+                    start_position = next_goose;
+                    goose_to_goose = true;
+                    break;
+                }
+            }
+            PositionType::Normal => break,
+            PositionType::End => {
+                host.update_winner(Some(player)).await?;
+                events
+                    .notify(Event::Win(player.clone()))
+                    .await
+                    .map_err(Error::Events)?;
+                won = true;
+                break;
+            }
+        }
+
+        again = true;
+    }
+
+    let rules = host.extra_turns();
+    let streak = match turn.player {
+        Some(p) if p == *player => turn.extra_turns,
+        _ => 0,
+    };
+    let extra_turn = !won
+        && (goose_to_goose
+            || (rules.doubles && doubles)
+            || (rules.dice && start_position.is_dice()));
+
+    let turn = if extra_turn && streak < rules.cap {
+        events
+            .notify(Event::ExtraTurn(player.clone()))
+            .await
+            .map_err(Error::Events)?;
+        Turn {
+            player: Some(player.clone()),
+            extra_turns: streak + 1,
+        }
+    } else {
+        Turn {
+            player: next_player(host, player).await?,
+            extra_turns: 0,
+        }
+    };
+    host.update_turn(turn).await?;
+
+    Ok(())
+}
+
+async fn next_player<Player, P, R, II, H>(
+    host: &H,
+    player: &Player,
+) -> Result<Option<Player>, H::Error>
+where
+    R: Roll,
+    II: IntoIterator<Item = Player>,
+    H: Host<Player, P, R, II>,
+    Player: PartialEq,
+{
+    let (mut first, mut next, mut found) = (None, None, false);
+    for p in host.players().await? {
+        if found {
+            next = Some(p);
+            break;
+        }
+        found = p == *player;
+        first.get_or_insert(p);
+    }
+
+    Ok(next.or(first))
+}
+
+pub(crate) async fn roll_and_move_player<Player, P, R, II, H, E>(
+    host: &mut H,
+    player: &Player,
+    events: &mut E,
+) -> Result<(), Error<Player, R::Face, H::Error, E::Error>>
+where
+    R: Roll,
+    II: IntoIterator<Item = Player>,
+    H: Host<Player, P, R, II>,
+    E: Events<Player, P, R>,
+    Player: Clone + PartialEq,
+    R::Face: Clone + PartialEq,
+    R::Step: Copy,
+    P: Position<R::Step> + Copy,
+{
+    let roll = host.roll_dice();
+
+    move_player(host, player, roll, events).await
+}
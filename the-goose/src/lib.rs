@@ -1,45 +1,147 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub use core::{convert, ops};
 
+use core::convert::Infallible;
+
+use engine::Blocking;
+
+pub mod asynch;
+mod engine;
+#[cfg(feature = "alloc")]
+pub mod overlay;
+
 pub enum AddPosition<Position> {
     Bounced(Position, Position),
     Normal(Position),
 }
 
 #[derive(PartialEq, Debug)]
-pub enum Error<Player, StateError> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Error<Player, Face, StateError, EventsError> {
     Inner(StateError),
+    Events(EventsError),
     DuplicatePlayer(Player),
     PlayerNotFound(Player),
+    InvalidRoll(Player, Face),
+    NotYourTurn(Player),
+    InvalidPosition(Player, u32),
 }
 
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Command<Player, Roll> {
     Add(Player),
     Remove(Player),
-    Move(Player, Roll, Roll),
+    Move(Player, Roll),
     RollAndMove(Player),
+    SetPosition(Player, u32),
+    Kick(Player),
+    Rename(Player, Player),
+    Reset,
+    Rematch,
 }
 
-#[derive(PartialEq, Debug)]
-pub enum Event<Player, Position, Roll, II: IntoIterator<Item = Player>> {
-    Players(II),
-    Moved(Player, Position, Position),
-    MovedAgain(Player, Position, Position),
-    Roll(Player, Roll, Roll),
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Event<Player, Position, Roll> {
+    PlayerJoined(Player),
+    PlayerLeft(Player),
+    PlayerRenamed(Player, Player),
+    Moved(Player, Position, Position, Landing),
+    MovedAgain(Player, Position, Position, Landing),
+    Roll(Player, Roll),
     Bounced(Player),
     Return(Player, Position),
     Win(Player),
     Prank(Player, Position, Position),
-    Jump(Player, Position),
+    Jump(Player, Position, Landing),
+    GooseToGoose(Player, Position, Position, Landing),
+    ExtraTurn(Player),
+    PositionSet(Player, Position),
+    Kicked(Player),
+    Reset,
+    Rematch,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EventKind {
+    PlayerJoined,
+    PlayerLeft,
+    PlayerRenamed,
+    Moved,
+    MovedAgain,
+    Roll,
+    Bounced,
+    Return,
+    Win,
+    Prank,
+    Jump,
+    GooseToGoose,
+    ExtraTurn,
+    PositionSet,
+    Kicked,
+    Reset,
+    Rematch,
+}
+
+impl<Player, Position, Roll> Event<Player, Position, Roll> {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Event::PlayerJoined(_) => EventKind::PlayerJoined,
+            Event::PlayerLeft(_) => EventKind::PlayerLeft,
+            Event::PlayerRenamed(..) => EventKind::PlayerRenamed,
+            Event::Moved(..) => EventKind::Moved,
+            Event::MovedAgain(..) => EventKind::MovedAgain,
+            Event::Roll(..) => EventKind::Roll,
+            Event::Bounced(_) => EventKind::Bounced,
+            Event::Return(..) => EventKind::Return,
+            Event::Win(_) => EventKind::Win,
+            Event::Prank(..) => EventKind::Prank,
+            Event::Jump(..) => EventKind::Jump,
+            Event::GooseToGoose(..) => EventKind::GooseToGoose,
+            Event::ExtraTurn(_) => EventKind::ExtraTurn,
+            Event::PositionSet(..) => EventKind::PositionSet,
+            Event::Kicked(_) => EventKind::Kicked,
+            Event::Reset => EventKind::Reset,
+            Event::Rematch => EventKind::Rematch,
+        }
+    }
+
+    pub fn player(&self) -> Option<&Player> {
+        match self {
+            Event::Reset | Event::Rematch => None,
+            Event::PlayerJoined(player)
+            | Event::PlayerLeft(player)
+            | Event::PlayerRenamed(_, player)
+            | Event::Moved(player, ..)
+            | Event::MovedAgain(player, ..)
+            | Event::Roll(player, _)
+            | Event::Bounced(player)
+            | Event::Return(player, _)
+            | Event::Win(player)
+            | Event::Prank(player, ..)
+            | Event::Jump(player, ..)
+            | Event::GooseToGoose(player, ..)
+            | Event::ExtraTurn(player)
+            | Event::PositionSet(player, _)
+            | Event::Kicked(player) => Some(player),
+        }
+    }
 }
 
-impl<Player, E> From<E> for Error<Player, E> {
+impl<Player, Face, E, EventsError> From<E> for Error<Player, Face, E, EventsError> {
     fn from(e: E) -> Self {
         Error::Inner(e)
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PositionType {
     TheBridge,
     TheGoose,
@@ -47,15 +149,153 @@ pub enum PositionType {
     End,
 }
 
-pub trait Events<Player, Position, Roll, II: IntoIterator<Item = Player>>: Default {
+/// Why a player moved: the dice they rolled, a goose moving them again by the same
+/// roll, the bridge, or a goose flying them to the next one.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Cause {
+    Roll,
+    TheGoose,
+    TheBridge,
+    GooseToGoose,
+}
+
+/// Where a move ends: the type of the square the player lands on, after bouncing back
+/// if they overshoot the end, why they moved there, and how many moves came before it
+/// in the same command.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Landing {
+    pub square: PositionType,
+    pub cause: Cause,
+    pub sequence: u32,
+}
+
+/// What happens when a player lands on a goose: the Italian rule moves them again by
+/// the same roll, the Spanish "de oca a oca" flies them to the next goose and grants
+/// another roll. On the last goose there is nowhere to fly, so they only roll again.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GooseRule {
+    #[default]
+    MoveAgain,
+    GooseToGoose,
+}
+
+/// Which outcomes of a move grant the same player another roll, besides flying from
+/// goose to goose, and how many of them can be chained before the turn passes anyway.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtraTurns {
+    pub doubles: bool,
+    pub dice: bool,
+    pub cap: u8,
+}
+
+impl Default for ExtraTurns {
+    fn default() -> Self {
+        ExtraTurns {
+            doubles: false,
+            dice: false,
+            cap: 3,
+        }
+    }
+}
+
+/// Whose turn it is and how many extra turns in a row they have been granted.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Turn<Player> {
+    pub player: Option<Player>,
+    pub extra_turns: u8,
+}
+
+impl<Player> Default for Turn<Player> {
+    fn default() -> Self {
+        Turn {
+            player: None,
+            extra_turns: 0,
+        }
+    }
+}
+
+pub trait Face {
+    type Step;
+
+    fn steps(&self) -> Self::Step;
+}
+
+macro_rules! impl_face {
+    ($($t:ty),*) => {
+        $(
+            impl Face for $t {
+                type Step = $t;
+
+                fn steps(&self) -> Self::Step {
+                    *self
+                }
+            }
+        )*
+    };
+}
+
+impl_face!(u8, u16, u32, u64, usize);
+
+pub trait Roll {
+    type Step;
+    type Face;
+
+    fn steps(&self) -> Self::Step;
+    fn faces(&self) -> &[Self::Face];
+}
+
+impl<F: Face, const N: usize> Roll for [F; N]
+where
+    F::Step: ops::Add<Output = F::Step> + Default,
+{
+    type Step = F::Step;
+    type Face = F;
+
+    fn faces(&self) -> &[Self::Face] {
+        self
+    }
+
+    fn steps(&self) -> Self::Step {
+        self.iter()
+            .fold(F::Step::default(), |steps, face| steps + face.steps())
+    }
+}
+
+pub trait Events<Player, Position, Roll> {
     type Error;
 
-    fn notify(&mut self, event: Event<Player, Position, Roll, II>) -> Result<(), Self::Error>;
+    fn notify(&mut self, event: Event<Player, Position, Roll>) -> Result<(), Self::Error>;
+
+    fn begin(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn commit(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn rollback(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
 }
 
-pub trait Position<Roll>: Sized + convert::From<u32> {
-    fn add(self, r: Roll) -> AddPosition<Self>;
+pub trait Position<Step>: Sized + convert::From<u32> + convert::Into<u32> {
+    fn add(self, s: Step) -> AddPosition<Self>;
     fn get_type(&self) -> PositionType;
+    fn end() -> Self;
+
+    fn next_goose(&self) -> Option<Self> {
+        None
+    }
+
+    fn is_dice(&self) -> bool {
+        false
+    }
 }
 
 pub trait State<Player, Position, II: IntoIterator<Item = Player>> {
@@ -71,46 +311,60 @@ pub trait State<Player, Position, II: IntoIterator<Item = Player>> {
         player: &Player,
         position: &Position,
     ) -> Result<(), Self::Error>;
+
+    fn begin(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn commit(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn rollback(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn turn(&self) -> Result<Turn<Player>, Self::Error> {
+        Ok(Turn::default())
+    }
+
+    fn update_turn(&mut self, _turn: Turn<Player>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn update_winner(&mut self, _winner: Option<&Player>) -> Result<(), Self::Error> {
+        Ok(())
+    }
 }
 
-pub trait TheGoose<Player, P, R, II: IntoIterator<Item = Player>> {
+pub trait TheGoose<Player, P, R: Roll, II: IntoIterator<Item = Player>> {
     type State: State<Player, P, II>;
-    type Events: Events<Player, P, R, II>;
 
     #[allow(clippy::type_complexity)]
-    fn execute(
+    fn execute<E: Events<Player, P, R>>(
         &mut self,
         command: Command<Player, R>,
+        events: &mut E,
     ) -> Result<
-        Self::Events,
-        Error<Player, <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error>,
+        (),
+        Error<
+            Player,
+            R::Face,
+            <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error,
+            E::Error,
+        >,
     >
     where
-        Player: Copy,
-        R: ops::Add<Output = R> + Copy,
-        P: Position<R> + Copy,
+        Player: Clone + PartialEq,
+        R::Face: Clone + PartialEq,
+        R::Step: Copy,
+        P: Position<R::Step> + Copy,
     {
-        use Command::*;
-        use Event::*;
-
-        match command {
-            Add(player) => self.add_player(player).and_then(|_| {
-                Ok({
-                    let mut events = Self::Events::default();
-                    events.notify(Players(self.state().players()?)).ok();
-                    events
-                })
-            }),
-            Remove(player) => self.remove_player(&player).and_then(|_| {
-                Ok({
-                    let mut events = Self::Events::default();
-                    events.notify(Players(self.state().players()?)).ok();
-                    events
-                })
-            }),
-            Move(player, dice1, dice2) => self.move_player(&player, dice1, dice2),
-            RollAndMove(player) => self.roll_and_move_player(&player),
-        }
+        engine::now(engine::execute(
+            &mut Blocking(self),
+            command,
+            &mut Blocking(events),
+        ))
     }
 
     #[allow(clippy::type_complexity)]
@@ -119,13 +373,14 @@ pub trait TheGoose<Player, P, R, II: IntoIterator<Item = Player>> {
         player: Player,
     ) -> Result<
         (),
-        Error<Player, <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error>,
+        Error<
+            Player,
+            R::Face,
+            <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error,
+            Infallible,
+        >,
     > {
-        if self.state().get_player_position(&player)?.is_none() {
-            Ok(self.state_mut().add_player(player)?)
-        } else {
-            Err(Error::DuplicatePlayer(player))
-        }
+        engine::now(engine::add_player(&mut Blocking(self), player))
     }
 
     #[allow(clippy::type_complexity)]
@@ -134,121 +389,176 @@ pub trait TheGoose<Player, P, R, II: IntoIterator<Item = Player>> {
         player: &Player,
     ) -> Result<
         (),
-        Error<Player, <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error>,
-    > {
-        Ok(self.state_mut().remove_player(player)?)
+        Error<
+            Player,
+            R::Face,
+            <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error,
+            Infallible,
+        >,
+    >
+    where
+        Player: Clone + PartialEq,
+    {
+        engine::now(engine::remove_player(&mut Blocking(self), player))
     }
 
     #[allow(clippy::type_complexity)]
-    fn move_player(
+    fn rename_player(
         &mut self,
         player: &Player,
-        dice1: R,
-        dice2: R,
+        name: Player,
     ) -> Result<
-        Self::Events,
-        Error<Player, <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error>,
+        (),
+        Error<
+            Player,
+            R::Face,
+            <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error,
+            Infallible,
+        >,
     >
     where
-        R: ops::Add<Output = R> + Copy,
-        P: Position<R> + Copy,
-        Player: Clone,
+        Player: Clone + PartialEq,
     {
-        let initial_position = self
-            .state()
-            .get_player_position(player)?
-            .ok_or_else(|| Error::PlayerNotFound(player.clone()))?;
-
-        let mut start_position = initial_position;
-
-        let mut again = false;
-        let mut events = Self::Events::default();
-        events
-            .notify(Event::Roll(player.clone(), dice1, dice2))
-            .ok();
-        loop {
-            let end_position = match start_position.add(dice1 + dice2) {
-                AddPosition::Bounced(bounced_position, end_position) => {
-                    events
-                        .notify(if again {
-                            Event::MovedAgain(player.clone(), start_position, end_position)
-                        } else {
-                            Event::Moved(player.clone(), start_position, end_position)
-                        })
-                        .ok();
-                    events.notify(Event::Bounced(player.clone())).ok();
-                    events
-                        .notify(Event::Return(player.clone(), bounced_position))
-                        .ok();
-                    bounced_position
-                }
-                AddPosition::Normal(end_position) => {
-                    events
-                        .notify(if again {
-                            Event::MovedAgain(player.clone(), start_position, end_position)
-                        } else {
-                            Event::Moved(player.clone(), start_position, end_position)
-                        })
-                        .ok();
-                    end_position
-                }
-            };
-
-            let players = self.state().find_players_by_position(&end_position)?;
-
-            start_position = end_position;
-
-            self.state_mut()
-                .update_player_position(player, &start_position)?;
-
-            for p in players {
-                events
-                    .notify(Event::Prank(p.clone(), end_position, initial_position))
-                    .ok();
-                self.state_mut()
-                    .update_player_position(&p, &initial_position)?;
-            }
+        engine::now(engine::rename_player(&mut Blocking(self), player, name))
+    }
 
-            match start_position.get_type() {
-                PositionType::TheBridge => {
-                    start_position = 12.into();
-                    self.state_mut()
-                        .update_player_position(player, &start_position)?;
-                    events
-                        .notify(Event::Jump(player.clone(), start_position))
-                        .ok();
-                    break;
-                }
-                PositionType::TheGoose => {}
-                PositionType::Normal => break,
-                PositionType::End => {
-                    events.notify(Event::Win(player.clone())).ok();
-                    break;
-                }
-            }
+    #[allow(clippy::type_complexity)]
+    fn set_player_position(
+        &mut self,
+        player: &Player,
+        position: &P,
+    ) -> Result<
+        (),
+        Error<
+            Player,
+            R::Face,
+            <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error,
+            Infallible,
+        >,
+    >
+    where
+        Player: Clone,
+        P: Position<R::Step> + Copy,
+    {
+        engine::now(engine::set_player_position(
+            &mut Blocking(self),
+            player,
+            (*position).into(),
+        ))
+        .map(|_| ())
+    }
 
-            again = true;
-        }
+    #[allow(clippy::type_complexity)]
+    fn reset(
+        &mut self,
+    ) -> Result<
+        (),
+        Error<
+            Player,
+            R::Face,
+            <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error,
+            Infallible,
+        >,
+    >
+    where
+        P: From<u32>,
+    {
+        engine::now(engine::reset(&mut Blocking(self)))
+    }
 
-        Ok(events)
+    #[allow(clippy::type_complexity)]
+    fn move_player<E: Events<Player, P, R>>(
+        &mut self,
+        player: &Player,
+        roll: R,
+        events: &mut E,
+    ) -> Result<
+        (),
+        Error<
+            Player,
+            R::Face,
+            <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error,
+            E::Error,
+        >,
+    >
+    where
+        R::Face: Clone + PartialEq,
+        R::Step: Copy,
+        P: Position<R::Step> + Copy,
+        Player: Clone + PartialEq,
+    {
+        engine::now(engine::move_player(
+            &mut Blocking(self),
+            player,
+            roll,
+            &mut Blocking(events),
+        ))
     }
 
     #[allow(clippy::type_complexity)]
-    fn roll_and_move_player(
+    fn roll_and_move_player<E: Events<Player, P, R>>(
         &mut self,
         player: &Player,
+        events: &mut E,
     ) -> Result<
-        Self::Events,
-        Error<Player, <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error>,
+        (),
+        Error<
+            Player,
+            R::Face,
+            <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error,
+            E::Error,
+        >,
     >
     where
-        R: ops::Add<Output = R> + Copy,
-        P: Position<R> + Copy,
-        Player: Copy,
+        R::Face: Clone + PartialEq,
+        R::Step: Copy,
+        P: Position<R::Step> + Copy,
+        Player: Clone + PartialEq,
     {
-        let (dice1, dice2) = (self.roll_dice(), self.roll_dice());
+        engine::now(engine::roll_and_move_player(
+            &mut Blocking(self),
+            player,
+            &mut Blocking(events),
+        ))
+    }
 
-        self.move_player(player, dice1, dice2)
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::type_complexity)]
+    fn preview_move<E: Events<Player, P, R>>(
+        &self,
+        player: &Player,
+        roll: R,
+        events: &mut E,
+    ) -> Result<
+        (),
+        Error<
+            Player,
+            R::Face,
+            <<Self as TheGoose<Player, P, R, II>>::State as State<Player, P, II>>::Error,
+            E::Error,
+        >,
+    >
+    where
+        Self: Sized,
+        Player: Ord + Clone,
+        R: Clone,
+        R::Face: Clone + PartialEq,
+        R::Step: Copy,
+        P: Position<R::Step> + Copy + PartialEq,
+        II: FromIterator<Player>,
+    {
+        let mut dry_run = overlay::DryRun::new(self, roll.clone());
+
+        // Anyone can preview their move: the dry run plays it as their turn, keeping the
+        // streak if it already is.
+        if self.state().turn()?.player.as_ref() != Some(player) {
+            dry_run.state_mut().update_turn(Turn {
+                player: Some(player.clone()),
+                extra_turns: 0,
+            })?;
+        }
+
+        dry_run.execute(Command::Move(player.clone(), roll), events)
     }
 
     fn state(&self) -> &Self::State;
@@ -256,4 +566,14 @@ pub trait TheGoose<Player, P, R, II: IntoIterator<Item = Player>> {
     fn state_mut(&mut self) -> &mut Self::State;
 
     fn roll_dice(&mut self) -> R;
+
+    fn is_valid_face(&self, face: &R::Face) -> bool;
+
+    fn goose_rule(&self) -> GooseRule {
+        GooseRule::MoveAgain
+    }
+
+    fn extra_turns(&self) -> ExtraTurns {
+        ExtraTurns::default()
+    }
 }
@@ -0,0 +1,180 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::{ExtraTurns, GooseRule, Roll, State, TheGoose, Turn};
+
+pub struct Overlay<'a, Player, P, II, S> {
+    state: &'a S,
+    changes: BTreeMap<Player, Option<P>>,
+    turn: Option<Turn<Player>>,
+    players: PhantomData<II>,
+}
+
+impl<'a, Player, P, II, S> Overlay<'a, Player, P, II, S> {
+    pub fn new(state: &'a S) -> Self {
+        Overlay {
+            state,
+            changes: BTreeMap::new(),
+            turn: None,
+            players: PhantomData,
+        }
+    }
+
+    pub fn changes(&self) -> &BTreeMap<Player, Option<P>> {
+        &self.changes
+    }
+
+    pub fn into_changes(self) -> BTreeMap<Player, Option<P>> {
+        self.changes
+    }
+}
+
+impl<'a, Player, P, II, S> State<Player, P, II> for Overlay<'a, Player, P, II, S>
+where
+    Player: Ord + Clone,
+    P: PartialEq + Copy + From<u32>,
+    II: IntoIterator<Item = Player> + FromIterator<Player>,
+    S: State<Player, P, II>,
+{
+    type Error = S::Error;
+
+    fn get_player_position(&self, player: &Player) -> Result<Option<P>, Self::Error> {
+        match self.changes.get(player) {
+            Some(position) => Ok(*position),
+            None => self.state.get_player_position(player),
+        }
+    }
+
+    fn add_player(&mut self, player: Player) -> Result<(), Self::Error> {
+        self.changes.insert(player, Some(P::from(0)));
+
+        Ok(())
+    }
+
+    fn remove_player(&mut self, player: &Player) -> Result<(), Self::Error> {
+        self.changes.insert(player.clone(), None);
+
+        Ok(())
+    }
+
+    fn find_players_by_position(&self, position: &P) -> Result<II, Self::Error> {
+        let mut players = self
+            .state
+            .find_players_by_position(position)?
+            .into_iter()
+            .filter(|player| !self.changes.contains_key(player))
+            .collect::<Vec<_>>();
+        players.extend(
+            self.changes
+                .iter()
+                .filter(|(_, p)| p.as_ref() == Some(position))
+                .map(|(player, _)| player.clone()),
+        );
+        players.sort();
+
+        Ok(players.into_iter().collect())
+    }
+
+    fn players(&self) -> Result<II, Self::Error> {
+        let mut players = self
+            .state
+            .players()?
+            .into_iter()
+            .filter(|player| !self.changes.contains_key(player))
+            .collect::<Vec<_>>();
+        players.extend(
+            self.changes
+                .iter()
+                .filter(|(_, p)| p.is_some())
+                .map(|(player, _)| player.clone()),
+        );
+        players.sort();
+
+        Ok(players.into_iter().collect())
+    }
+
+    fn update_player_position(&mut self, player: &Player, position: &P) -> Result<(), Self::Error> {
+        if self.get_player_position(player)?.is_some() {
+            self.changes.insert(player.clone(), Some(*position));
+        }
+
+        Ok(())
+    }
+
+    fn turn(&self) -> Result<Turn<Player>, Self::Error> {
+        match &self.turn {
+            Some(turn) => Ok(turn.clone()),
+            None => self.state.turn(),
+        }
+    }
+
+    fn update_turn(&mut self, turn: Turn<Player>) -> Result<(), Self::Error> {
+        self.turn = Some(turn);
+
+        Ok(())
+    }
+}
+
+pub struct DryRun<'a, Player, P, R, II, G, S> {
+    the_goose: &'a G,
+    state: Overlay<'a, Player, P, II, S>,
+    roll: R,
+}
+
+impl<'a, Player, P, R, II, G, S> DryRun<'a, Player, P, R, II, G, S> {
+    pub fn into_changes(self) -> BTreeMap<Player, Option<P>> {
+        self.state.into_changes()
+    }
+}
+
+impl<'a, Player, P, R, II, G> DryRun<'a, Player, P, R, II, G, G::State>
+where
+    R: Roll,
+    II: IntoIterator<Item = Player>,
+    G: TheGoose<Player, P, R, II>,
+{
+    pub fn new(the_goose: &'a G, roll: R) -> Self {
+        DryRun {
+            the_goose,
+            state: Overlay::new(the_goose.state()),
+            roll,
+        }
+    }
+}
+
+impl<'a, Player, P, R, II, G, S> TheGoose<Player, P, R, II> for DryRun<'a, Player, P, R, II, G, S>
+where
+    Player: Ord + Clone,
+    P: PartialEq + Copy + From<u32>,
+    R: Roll + Clone,
+    II: IntoIterator<Item = Player> + FromIterator<Player>,
+    G: TheGoose<Player, P, R, II, State = S>,
+    S: State<Player, P, II>,
+{
+    type State = Overlay<'a, Player, P, II, S>;
+
+    fn state(&self) -> &Self::State {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+
+    fn roll_dice(&mut self) -> R {
+        self.roll.clone()
+    }
+
+    fn is_valid_face(&self, face: &R::Face) -> bool {
+        self.the_goose.is_valid_face(face)
+    }
+
+    fn goose_rule(&self) -> GooseRule {
+        self.the_goose.goose_rule()
+    }
+
+    fn extra_turns(&self) -> ExtraTurns {
+        self.the_goose.extra_turns()
+    }
+}